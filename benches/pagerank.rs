@@ -0,0 +1,57 @@
+//! Measures the time of reading and of calculating the pagerank of the 
+//! Barabási graphs of the data directory, to tell which one dominates. Run 
+//! it with `cargo bench`; the names of graphs given as arguments select 
+//! some of them.
+
+use std::{env, path::Path, time::Duration};
+
+use pagerank::Table;
+
+/// The graphs that are measured, by number of nodes.
+const SIZES: [usize; 5] = [20000, 40000, 60000, 80000, 100000];
+
+/// The number of times each graph is read and ranked; the fastest run of 
+/// each phase is reported.
+const RUNS: usize = 5;
+
+/// Reads the graph of the file and calculates its pagerank, returning the 
+/// duration of each phase and the number of iterations.
+fn run(path: &Path) -> (Duration, Duration, usize) {
+    let mut t = Table::new();
+    t.set_quiet(true);
+    t.set_timing(true);
+    t.set_numeric(true);
+    t.set_delim(" ");
+    t.read_file(&path.to_path_buf()).expect("graph file read");
+    let result = t.pagerank().expect("pagerank calculated");
+    (
+        t.get_read_time().expect("read timed"),
+        t.get_pagerank_time().expect("pagerank timed"),
+        result.iterations,
+    )
+}
+
+fn main() {
+    // cargo bench passes --bench, which is not a graph name
+    let filters: Vec<String> = env::args().skip(1).filter(|a| !a.starts_with('-')).collect();
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+
+    for size in SIZES {
+        let name = format!("barabasi-{}.txt", size);
+        if !filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())) {
+            continue;
+        }
+
+        let path = data.join(&name);
+        let mut read = Duration::MAX;
+        let mut rank = Duration::MAX;
+        let mut iterations = 0;
+        for _ in 0..RUNS {
+            let (r, p, i) = run(&path);
+            read = read.min(r);
+            rank = rank.min(p);
+            iterations = i;
+        }
+        println!("{}: read {:.3?}, pagerank {:.3?} ({} iterations)", name, read, rank, iterations);
+    }
+}
@@ -0,0 +1,11 @@
+use ndarray::Array1;
+
+use crate::table::{PrFloat, Table};
+
+impl Table {
+    /// Returns a copy of the pagerank vector as an ndarray array, by node 
+    /// index; it is empty if no pagerank was calculated.
+    pub fn pagerank_array(&self) -> Array1<PrFloat> {
+        Array1::from(self.pagerank_scores().to_vec())
+    }
+}
@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use crate::{
+    error::PagerankError,
+    norm::ConvergenceNorm,
+    table::{DanglingPolicy, IterationMethod, PrFloat, SelfLoopPolicy, Table, Verbosity},
+};
+
+/// A builder for configuring a [`Table`] before reading a graph. The 
+/// parameters are validated as they are set, and the first invalid one is 
+/// reported when the table is built.
+pub struct TableBuilder {
+    table: Table,
+    error: Option<PagerankError>,
+}
+
+impl Default for TableBuilder {
+    fn default() -> Self {
+        Self { table: Table::new(), error: None }
+    }
+}
+
+impl TableBuilder {
+    pub fn new() -> TableBuilder {
+        Default::default()
+    }
+
+    /// Sets tracing output.
+    pub fn trace(mut self, t: bool) -> Self {
+        self.table.set_trace(t);
+        self
+    }
+
+    /// Sets how much the table reports about its work.
+    pub fn verbosity(mut self, v: Verbosity) -> Self {
+        self.table.set_verbosity(v);
+        self
+    }
+
+    /// Specifies whether the progress output while reading is suppressed.
+    pub fn quiet(mut self, q: bool) -> Self {
+        self.table.set_quiet(q);
+        self
+    }
+
+    /// Specifies whether the durations of reading and of the pagerank 
+    /// calculation are measured.
+    pub fn timing(mut self, t: bool) -> Self {
+        self.table.set_timing(t);
+        self
+    }
+
+    /// Sets the number of lines read between progress reports; zero reports
+    /// the progress only at the end of each input.
+    pub fn progress_interval(mut self, n: usize) -> Self {
+        self.table.set_progress_interval(n);
+        self
+    }
+
+    /// Sets the pagerank damping factor; it must be in [0, 1).
+    pub fn alpha(mut self, a: PrFloat) -> Self {
+        let r = self.table.set_alpha(a);
+        self.keep_error(r)
+    }
+
+    /// Sets a teleport probability independent of the damping factor; it 
+    /// must be in (0, 1].
+    pub fn teleport(mut self, beta: PrFloat) -> Self {
+        let r = self.table.set_teleport(beta);
+        self.keep_error(r)
+    }
+
+    /// Sets the convergence criterion; it must be positive.
+    pub fn convergence(mut self, c: PrFloat) -> Self {
+        let r = self.table.set_convergence(c);
+        self.keep_error(r)
+    }
+
+    /// Sets the norm of the difference checked against the convergence 
+    /// criterion.
+    pub fn convergence_norm(mut self, n: ConvergenceNorm) -> Self {
+        self.table.set_convergence_norm(n);
+        self
+    }
+
+    /// Sets the maximum number of iterations; it must be positive.
+    pub fn max_iterations(mut self, i: usize) -> Self {
+        let r = self.table.set_max_iterations(i);
+        self.keep_error(r)
+    }
+
+    /// Sets a number of iterations to perform regardless of convergence.
+    pub fn fixed_iterations(mut self, n: Option<usize>) -> Self {
+        self.table.set_fixed_iterations(n);
+        self
+    }
+
+    /// Sets the time after which the iterations stop, if any.
+    pub fn time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.table.set_time_budget(budget);
+        self
+    }
+
+    /// Sets the largest vertex index accepted from the input, if any.
+    pub fn max_index(mut self, max: Option<usize>) -> Self {
+        self.table.set_max_index(max);
+        self
+    }
+
+    /// Specifies whether each calculation starts from the pagerank of the 
+    /// last one.
+    pub fn warm_start(mut self, w: bool) -> Self {
+        self.table.set_warm_start(w);
+        self
+    }
+
+    /// Specifies whether the graph data are in numeric form.
+    pub fn numeric(mut self, n: bool) -> Self {
+        self.table.set_numeric(n);
+        self
+    }
+
+    /// Specifies whether the edges of the graph are weighted.
+    pub fn weighted(mut self, w: bool) -> Self {
+        self.table.set_weighted(w);
+        self
+    }
+
+    /// Specifies whether repeated edges are counted instead of ignored.
+    pub fn allow_multi_edges(mut self, m: bool) -> Self {
+        self.table.set_allow_multi_edges(m);
+        self
+    }
+
+    /// Specifies whether each edge adds the reverse arc as well.
+    pub fn undirected(mut self, u: bool) -> Self {
+        self.table.set_undirected(u);
+        self
+    }
+
+    /// Specifies whether an edge whose reverse was read before is skipped.
+    pub fn dedup_symmetric(mut self, d: bool) -> Self {
+        self.table.set_dedup_symmetric(d);
+        self
+    }
+
+    /// Specifies whether each edge adds the reverse arc instead.
+    pub fn reverse(mut self, r: bool) -> Self {
+        self.table.set_reverse(r);
+        self
+    }
+
+    /// Specifies whether isolated nodes are left out of the uniform teleport.
+    pub fn ignore_isolated(mut self, i: bool) -> Self {
+        self.table.set_ignore_isolated(i);
+        self
+    }
+
+    /// Sets the method used to calculate each pagerank iteration.
+    pub fn iteration_method(mut self, m: IterationMethod) -> Self {
+        self.table.set_iteration_method(m);
+        self
+    }
+
+    /// Sets how the pagerank of dangling nodes is distributed.
+    pub fn dangling_policy(mut self, p: DanglingPolicy) -> Self {
+        self.table.set_dangling_policy(p);
+        self
+    }
+
+    /// Sets how self-loops are handled.
+    pub fn self_loops(mut self, p: SelfLoopPolicy) -> Self {
+        self.table.set_self_loops(p);
+        self
+    }
+
+    /// Sets the delimiter used for reading the graph data file.
+    pub fn delim(mut self, d: &str) -> Self {
+        self.table.set_delim(d);
+        self
+    }
+
+    /// Sets several delimiters for reading the graph data file; each line 
+    /// is split at the first of them found in it.
+    pub fn delims(mut self, d: &[&str]) -> Self {
+        self.table.set_delims(d);
+        self
+    }
+
+    /// Specifies whether the fields of the input are separated by runs of 
+    /// whitespace instead of the delimiter.
+    pub fn whitespace(mut self, w: bool) -> Self {
+        self.table.set_whitespace(w);
+        self
+    }
+
+    /// Specifies whether double quotes around the fields of the graph data 
+    /// file are removed.
+    pub fn trim_quotes(mut self, q: bool) -> Self {
+        self.table.set_trim_quotes(q);
+        self
+    }
+
+    /// Specifies whether node names that differ only in case are the same 
+    /// node.
+    pub fn case_insensitive(mut self, c: bool) -> Self {
+        self.table.set_case_insensitive(c);
+        self
+    }
+
+    /// Sets the prefix of the comment lines of the graph data file.
+    pub fn comment_prefix(mut self, p: &str) -> Self {
+        self.table.set_comment_prefix(p);
+        self
+    }
+
+    /// Sets the number of header lines of the graph data file.
+    pub fn skip_header(mut self, n: usize) -> Self {
+        self.table.set_skip_header(n);
+        self
+    }
+
+    /// Specifies whether lines without the delimiter are treated as errors.
+    pub fn strict(mut self, s: bool) -> Self {
+        self.table.set_strict(s);
+        self
+    }
+
+    /// Specifies whether lines with invalid numeric vertices are skipped 
+    /// instead of treated as errors.
+    pub fn lenient(mut self, l: bool) -> Self {
+        self.table.set_lenient(l);
+        self
+    }
+
+    /// Specifies whether files are scanned to count their vertices before 
+    /// they are read.
+    pub fn count_first(mut self, c: bool) -> Self {
+        self.table.set_count_first(c);
+        self
+    }
+
+    /// Sets the number of decimal places of the pagerank values in the text
+    /// output, zero for full precision.
+    pub fn output_precision(mut self, p: usize) -> Self {
+        self.table.set_output_precision(p);
+        self
+    }
+
+    /// Keeps the error of setting a parameter, unless an earlier one failed.
+    fn keep_error(mut self, r: Result<(), PagerankError>) -> Self {
+        if let Err(e) = r {
+            self.error.get_or_insert(e);
+        }
+        self
+    }
+
+    /// Returns the configured table, or the error of the first invalid 
+    /// parameter.
+    pub fn build(self) -> Result<Table, PagerankError> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.table),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{error::PagerankError, table::PrFloat};
+
+/// The first line of a checkpoint.
+const HEADER: &str = "pagerank checkpoint";
+
+/// The state of a pagerank calculation after some iteration: the number of
+/// iterations performed, the difference of the last one and the pagerank 
+/// vector it produced.
+pub(crate) struct Checkpoint {
+    pub(crate) iterations: usize,
+    pub(crate) diff: PrFloat,
+    pub(crate) pr: Vec<PrFloat>,
+}
+
+/// Writes a checkpoint as lines of text: the header, then 
+/// iterations = <n>, diff = <diff> and nodes = <length>, then each value of
+/// the pagerank vector on a line of its own. The values are written with 
+/// full precision, so a calculation resumed from them continues exactly.
+pub(crate) fn write<W: Write>(
+    w: &mut W,
+    iterations: usize,
+    diff: PrFloat,
+    pr: &[PrFloat],
+) -> io::Result<()> {
+    writeln!(w, "{}", HEADER)?;
+    writeln!(w, "iterations = {}", iterations)?;
+    writeln!(w, "diff = {:?}", diff)?;
+    writeln!(w, "nodes = {}", pr.len())?;
+    for x in pr {
+        writeln!(w, "{:?}", x)?;
+    }
+    w.flush()
+}
+
+/// Reads a checkpoint written by write() for a graph with the given number
+/// of nodes.
+/// 
+/// Returns an InvalidCheckpoint error for invalid contents, with the 
+/// (one-based) number of the offending line: a number of nodes other than 
+/// the expected one, or a pagerank value that is negative or not finite.
+pub(crate) fn read<R: BufRead>(r: R, expected_nodes: usize) -> Result<Checkpoint, PagerankError> {
+    let mut lines = r.lines();
+    let mut line = 0;
+    let mut next = |what: &str| -> Result<(usize, String), PagerankError> {
+        line += 1;
+        match lines.next() {
+            Some(l) => Ok((line, l?)),
+            None => Err(invalid(line, format!("missing {}", what))),
+        }
+    };
+
+    let (n, header) = next("header")?;
+    if header.trim() != HEADER {
+        return Err(invalid(n, "not a pagerank checkpoint".to_string()));
+    }
+    let (n, l) = next("iterations")?;
+    let iterations = parse_field(n, &l, "iterations")?;
+    let (n, l) = next("diff")?;
+    let diff = parse_field(n, &l, "diff")?;
+    let (n, l) = next("nodes")?;
+    let nodes: usize = parse_field(n, &l, "nodes")?;
+    // Checked before allocating, so a corrupt count cannot exhaust memory
+    if nodes != expected_nodes {
+        return Err(invalid(n, format!("{} nodes, but the graph has {}", nodes, expected_nodes)));
+    }
+
+    let mut pr = Vec::with_capacity(nodes);
+    for _ in 0..nodes {
+        let (n, l) = next("pagerank value")?;
+        let x = l.trim()
+            .parse()
+            .ok()
+            .filter(|x: &PrFloat| x.is_finite() && *x >= 0.0)
+            .ok_or_else(|| invalid(n, format!("invalid pagerank value '{}'", l.trim())))?;
+        pr.push(x);
+    }
+
+    Ok(Checkpoint { iterations, diff, pr })
+}
+
+/// Parses a line of the form <name> = <value>.
+fn parse_field<T: std::str::FromStr>(
+    line: usize,
+    s: &str,
+    name: &str,
+) -> Result<T, PagerankError> {
+    s.split_once('=')
+        .filter(|(key, _)| key.trim() == name)
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .ok_or_else(|| invalid(line, format!("expected {} = <value>", name)))
+}
+
+fn invalid(line: usize, reason: String) -> PagerankError {
+    PagerankError::InvalidCheckpoint { line, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a checkpoint after 3 iterations with the given nodes line 
+    /// and pagerank values.
+    fn checkpoint(nodes: &str, values: &[&str]) -> String {
+        let mut s = format!("{}\niterations = 3\ndiff = 0.25\n{}\n", HEADER, nodes);
+        for v in values {
+            s += v;
+            s += "\n";
+        }
+        s
+    }
+
+    /// Returns the line of an InvalidCheckpoint error.
+    fn error_line(r: Result<Checkpoint, PagerankError>) -> usize {
+        match r {
+            Err(PagerankError::InvalidCheckpoint { line, .. }) => line,
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("invalid checkpoint was read"),
+        }
+    }
+
+    #[test]
+    fn written_checkpoint_reads_back_exactly() {
+        let pr = [0.1, 1.0 / 3.0, 0.0, 1.0 - 0.1 - 1.0 / 3.0];
+        let mut out = Vec::new();
+        write(&mut out, 7, 1e-3, &pr).unwrap();
+
+        let c = read(out.as_slice(), pr.len()).unwrap();
+        assert_eq!(c.iterations, 7);
+        assert_eq!(c.diff, 1e-3);
+        assert_eq!(c.pr, pr);
+    }
+
+    #[test]
+    fn wrong_node_count_is_rejected_before_allocating() {
+        let s = checkpoint("nodes = 18446744073709551615", &["0.5", "0.5"]);
+        assert_eq!(error_line(read(s.as_bytes(), 2)), 4);
+
+        let s = checkpoint("nodes = 3", &["0.5", "0.5", "0.0"]);
+        assert_eq!(error_line(read(s.as_bytes(), 2)), 4);
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        for v in ["NaN", "inf", "-0.5", "0.5x"] {
+            let s = checkpoint("nodes = 2", &["0.5", v]);
+            assert_eq!(error_line(read(s.as_bytes(), 2)), 6, "{}", v);
+        }
+    }
+
+    #[test]
+    fn truncated_checkpoint_is_rejected() {
+        let s = checkpoint("nodes = 2", &["0.5"]);
+        assert_eq!(error_line(read(s.as_bytes(), 2)), 6);
+
+        let s = format!("{}\niterations = 3\n", HEADER);
+        assert_eq!(error_line(read(s.as_bytes(), 2)), 3);
+
+        assert_eq!(error_line(read("nodes = 2\n".as_bytes(), 2)), 1);
+    }
+}
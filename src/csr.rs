@@ -0,0 +1,79 @@
+use crate::table::PrFloat;
+
+/// A compressed sparse row representation of the hyperlink matrix. The 
+/// sources of all the rows are stored in a single vector, along with the 
+/// corresponding elements of the H matrix; the sources of row i are 
+/// sources[offsets[i]..offsets[i + 1]].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Csr {
+    offsets: Vec<usize>,
+    sources: Vec<usize>,
+    values: Vec<PrFloat>,
+}
+
+impl Csr {
+    /// Flattens the given rows into their compressed representation. The
+    /// value of each link is calculated by h from its source and its position
+    /// in the row.
+    pub(crate) fn from_rows<F>(rows: &[Vec<usize>], h: F) -> Csr
+        where F: Fn(usize, usize, usize) -> PrFloat
+    {
+        let num_links = rows.iter().map(Vec::len).sum();
+        let mut offsets = Vec::with_capacity(rows.len() + 1);
+        let mut sources = Vec::with_capacity(num_links);
+        let mut values = Vec::with_capacity(num_links);
+
+        offsets.push(0);
+        for (i, row) in rows.iter().enumerate() {
+            for (j, ci) in row.iter().enumerate() {
+                sources.push(*ci);
+                values.push(h(i, j, *ci));
+            }
+            offsets.push(sources.len());
+        }
+
+        Csr { offsets, sources, values }
+    }
+
+    /// Returns the transpose of the matrix, whose row j holds the rows that
+    /// have j as a source, in increasing order, with the same values.
+    pub(crate) fn transpose(&self) -> Csr {
+        let num_rows = self.offsets.len() - 1;
+        let mut offsets = vec![0; num_rows + 1];
+        for ci in &self.sources {
+            offsets[ci + 1] += 1;
+        }
+        for i in 0..num_rows {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let mut sources = vec![0; self.sources.len()];
+        let mut values = vec![0.0; self.values.len()];
+        for i in 0..num_rows {
+            for (ci, h_v) in self.row(i).iter().zip(self.values(i)) {
+                sources[next[*ci]] = i;
+                values[next[*ci]] = *h_v;
+                next[*ci] += 1;
+            }
+        }
+
+        Csr { offsets, sources, values }
+    }
+
+    /// Returns the sources of row i.
+    pub(crate) fn row(&self, i: usize) -> &[usize] {
+        &self.sources[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Returns the H matrix elements of row i, in the order of the sources.
+    pub(crate) fn values(&self, i: usize) -> &[PrFloat] {
+        &self.values[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Returns the offsets, the sources and the values of the matrix.
+    #[cfg(feature = "sprs")]
+    pub(crate) fn into_parts(self) -> (Vec<usize>, Vec<usize>, Vec<PrFloat>) {
+        (self.offsets, self.sources, self.values)
+    }
+}
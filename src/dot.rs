@@ -0,0 +1,16 @@
+use std::io::{self, Write};
+
+/// Writes s as a quoted Graphviz ID, escaping the quotes and backslashes it
+/// contains.
+pub(crate) fn write_id<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write!(w, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            c => write!(w, "{}", c)?,
+        }
+    }
+    write!(w, "\"")
+}
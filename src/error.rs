@@ -0,0 +1,126 @@
+use std::{error, fmt, io};
+
+use crate::table::PrFloat;
+
+/// Errors that can occur while reading a graph or calculating its pagerank.
+#[derive(Debug)]
+pub enum PagerankError {
+    /// An I/O error occurred while reading the input.
+    Io(io::Error),
+    /// A vertex could not be parsed as a numeric index. The line number
+    /// starts from one.
+    ParseVertex { line: usize, token: String },
+    /// A numeric vertex exceeds the maximum index. The line number starts 
+    /// from one.
+    IndexOutOfRange { line: usize, index: usize, max: usize },
+    /// An edge weight could not be parsed as a non-negative number. The line
+    /// number starts from one.
+    ParseWeight { line: usize, token: String },
+    /// A node named in a file of node weights is not in the graph. The line
+    /// number starts from one.
+    UnknownNode { line: usize, name: String },
+    /// A line of the input does not contain the delimiter. The line number
+    /// starts from one.
+    MissingDelimiter { line: usize },
+    /// The Matrix Market input has an invalid or unsupported header, 
+    /// dimensions line or entry. The line number starts from one.
+    InvalidMatrixMarket { line: usize, reason: String },
+    /// A checkpoint has invalid contents, or does not match the graph. The
+    /// line number starts from one, or is zero if the whole checkpoint is 
+    /// at fault.
+    InvalidCheckpoint { line: usize, reason: String },
+    /// The damping factor is outside [0, 1).
+    InvalidAlpha(PrFloat),
+    /// The teleport probability is outside (0, 1].
+    InvalidTeleport(PrFloat),
+    /// The convergence criterion is not positive.
+    InvalidConvergence(PrFloat),
+    /// The maximum number of iterations is zero.
+    InvalidMaxIterations,
+    /// An edge weight is negative or not finite.
+    InvalidWeight(f64),
+    /// The pagerank vector is not a probability distribution: some value is
+    /// not finite or they do not sum to one. The sum is included.
+    Diverged(PrFloat),
+    /// The personalization weights are negative, not finite, or sum to zero.
+    InvalidPersonalization,
+    /// The pagerank of a graph without nodes cannot be calculated.
+    EmptyGraph,
+    /// Tables cannot be merged because one has numeric input and the other
+    /// does not.
+    MergeMismatch,
+}
+
+impl fmt::Display for PagerankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PagerankError::Io(e) => write!(f, "{}", e),
+            PagerankError::ParseVertex { line, token } => {
+                write!(f, "line {}: invalid numeric vertex '{}'", line, token)
+            }
+            PagerankError::IndexOutOfRange { line, index, max } => {
+                write!(f, "line {}: vertex index {} exceeds the maximum index {}", line, index, max)
+            }
+            PagerankError::ParseWeight { line, token } => {
+                write!(f, "line {}: invalid weight '{}'", line, token)
+            }
+            PagerankError::UnknownNode { line, name } => {
+                write!(f, "line {}: unknown node '{}'", line, name)
+            }
+            PagerankError::MissingDelimiter { line } => {
+                write!(f, "line {}: missing delimiter", line)
+            }
+            PagerankError::InvalidMatrixMarket { line, reason } => {
+                write!(f, "line {}: invalid Matrix Market input: {}", line, reason)
+            }
+            PagerankError::InvalidCheckpoint { line: 0, reason } => {
+                write!(f, "invalid checkpoint: {}", reason)
+            }
+            PagerankError::InvalidCheckpoint { line, reason } => {
+                write!(f, "line {}: invalid checkpoint: {}", line, reason)
+            }
+            PagerankError::InvalidAlpha(a) => {
+                write!(f, "invalid alpha {} (must be in [0, 1))", a)
+            }
+            PagerankError::InvalidTeleport(b) => {
+                write!(f, "invalid teleport probability {} (must be in (0, 1])", b)
+            }
+            PagerankError::InvalidConvergence(c) => {
+                write!(f, "invalid convergence {} (must be positive)", c)
+            }
+            PagerankError::InvalidMaxIterations => {
+                write!(f, "invalid maximum number of iterations (must be positive)")
+            }
+            PagerankError::InvalidWeight(w) => {
+                write!(f, "invalid weight {} (must be non-negative)", w)
+            }
+            PagerankError::Diverged(sum) => {
+                write!(f, "pagerank diverged (values must be finite and sum to one, sum is {})", sum)
+            }
+            PagerankError::InvalidPersonalization => {
+                write!(f, "invalid personalization (weights must be non-negative with a positive sum)")
+            }
+            PagerankError::EmptyGraph => {
+                write!(f, "the graph is empty (no nodes were read)")
+            }
+            PagerankError::MergeMismatch => {
+                write!(f, "cannot merge tables with numeric and string input")
+            }
+        }
+    }
+}
+
+impl error::Error for PagerankError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PagerankError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PagerankError {
+    fn from(e: io::Error) -> Self {
+        PagerankError::Io(e)
+    }
+}
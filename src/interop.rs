@@ -0,0 +1,52 @@
+use petgraph::{graph::{Graph, NodeIndex}, Directed};
+
+use crate::table::Table;
+
+impl Table {
+    /// Creates a numeric table from a petgraph graph, with a node for each 
+    /// node of the graph, by index, and an arc for each edge. The table is
+    /// ready for pagerank().
+    pub fn from_petgraph<N, E>(g: &Graph<N, E, Directed>) -> Table {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_num_rows(g.node_count());
+        for e in g.raw_edges() {
+            t.add_edge_idx(e.source().index(), e.target().index());
+        }
+        t
+    }
+
+    /// Creates a table from a petgraph graph, naming each node by calling 
+    /// name with its weight, and with an arc for each edge. Nodes with the 
+    /// same name are the same node of the table. The table is ready for 
+    /// pagerank().
+    pub fn from_petgraph_with<N, E, F>(g: &Graph<N, E, Directed>, name: F) -> Table
+        where F: Fn(&N) -> String
+    {
+        let mut t = Table::new();
+        t.set_numeric(false);
+        let idx: Vec<usize> = g
+            .node_indices()
+            .map(|n| t.insert_mapping(name(&g[n])))
+            .collect();
+        t.set_num_rows(t.get_mapping().len());
+        for e in g.raw_edges() {
+            t.add_edge_idx(idx[e.source().index()], idx[e.target().index()]);
+        }
+        t
+    }
+
+    /// Converts the graph of the table to a petgraph graph, with a node for
+    /// each row, by index, weighted by the node name, and an edge for each 
+    /// arc.
+    pub fn to_petgraph(&self) -> Graph<String, ()> {
+        let mut g = Graph::with_capacity(self.get_num_rows(), self.num_edges());
+        for i in 0..self.get_num_rows() {
+            g.add_node(self.get_node_name(i));
+        }
+        for (from, to) in self.edges() {
+            g.add_edge(NodeIndex::new(from), NodeIndex::new(to), ());
+        }
+        g
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::{self, Write};
+
+use crate::table::PrFloat;
+
+/// Writes s as a JSON string, with the necessary escapes.
+pub(crate) fn write_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write!(w, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            c if (c as u32) < 0x20 => write!(w, "\\u{:04x}", c as u32)?,
+            c => write!(w, "{}", c)?,
+        }
+    }
+    write!(w, "\"")
+}
+
+/// Writes x as a JSON number, with full precision. JSON has no 
+/// representation for NaN and infinite values, so they are written as null.
+pub(crate) fn write_number<W: Write>(w: &mut W, x: PrFloat) -> io::Result<()> {
+    if x.is_finite() {
+        write!(w, "{:?}", x)
+    } else {
+        write!(w, "null")
+    }
+}
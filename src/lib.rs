@@ -0,0 +1,31 @@
+//! A simple PageRank implementation.
+//!
+//! The [`Table`] type reads a graph, either from a file or built in memory,
+//! and calculates the pagerank vector of its hyperlink matrix.
+
+#[cfg(feature = "ndarray")]
+mod array;
+mod builder;
+mod checkpoint;
+mod csr;
+mod dot;
+mod error;
+#[cfg(feature = "petgraph")]
+mod interop;
+mod json;
+mod norm;
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "sprs")]
+mod sparse;
+mod table;
+
+pub use crate::builder::TableBuilder;
+pub use crate::error::PagerankError;
+pub use crate::norm::ConvergenceNorm;
+pub use crate::table::{
+    DanglingPolicy, GraphStats, IterationCallback, IterationMethod, PagerankResult, PrFloat,
+    ProgressCallback, SelfLoopPolicy, SortOrder, Table, Verbosity, DEFAULT_ALPHA,
+    DEFAULT_COMMENT_PREFIX, DEFAULT_CONVERGENCE, DEFAULT_DELIM, DEFAULT_MAX_ITERATIONS,
+    DEFAULT_NUMERIC, DEFAULT_PROGRESS_INTERVAL,
+};
@@ -1,97 +1,696 @@
-use std::{process::exit, path::{PathBuf}};
-use clap::Parser;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::exit,
+    time::Duration,
+};
+use clap::{ArgAction, Parser, ValueEnum};
+use log::LevelFilter;
 
-mod table;
-use crate::table::Table;
+use pagerank::{ConvergenceNorm, PagerankError, PrFloat, Table, TableBuilder, Verbosity};
+
+/// The number of iterations performed to estimate the number of iterations
+/// of the calculation.
+const PROBE_ITERATIONS: usize = 3;
+
+/// The default fraction of dangling vertices above which --stats warns.
+const DANGLING_WARNING_FRACTION: f64 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// <node> = <pagerank value> lines
+    Text,
+    /// a JSON object with the nodes and their pagerank values
+    Json,
+    /// a Graphviz digraph annotated with the pagerank values
+    Dot,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Algorithm {
+    /// the pagerank of the nodes
+    Pagerank,
+    /// the authority and hub scores of the nodes
+    Hits,
+    /// the eigenvector centrality of the nodes, without damping
+    Eigenvector,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Norm {
+    /// the sum of the absolute differences of the pagerank values
+    L1,
+    /// the Euclidean length of the difference of the pagerank vectors
+    L2,
+    /// the largest absolute difference of any pagerank value
+    Linf,
+}
+
+impl From<Norm> for ConvergenceNorm {
+    fn from(n: Norm) -> Self {
+        match n {
+            Norm::L1 => ConvergenceNorm::L1,
+            Norm::L2 => ConvergenceNorm::L2,
+            Norm::Linf => ConvergenceNorm::LInf,
+        }
+    }
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// enable tracing
+    /// enable tracing; the same as -vv
     #[arg(short, long)]
     t: bool,
 
+    /// log the difference of each iteration; repeated, log everything, as
+    /// tracing does
+    #[arg(short, action=ArgAction::Count)]
+    v: u8,
+
+    /// TOML file, or JSON file with a .json extension, with the values of 
+    /// alpha, convergence, max_iterations, numeric, delim and size; the 
+    /// options given on the command line override them, and those in 
+    /// neither take their default values. Requires the config feature
+    #[arg(long, value_name="path")]
+    config: Option<PathBuf>,
+
+    /// print only the results to stdout, without the parameters and progress
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// print a summary of the graph that was read to stderr
+    #[arg(long)]
+    stats: bool,
+
+    /// with --stats, warn if the fraction of dangling vertices exceeds this
+    #[arg(long, value_name="fraction", default_value_t=DANGLING_WARNING_FRACTION)]
+    dangling_warning: f64,
+
+    /// only read and check the graph, printing its summary and the number 
+    /// of skipped lines, without calculating anything
+    #[arg(long)]
+    validate: bool,
+
+    /// print the time taken to read the graph and to calculate the pagerank
+    /// to stderr
+    #[arg(long)]
+    timing: bool,
+
     /// treat graph file as numeric; i.e. input comprises integer vertex names
     #[arg(short, long)]
     n: bool,
 
-    /// the dumping factor
-    #[arg(short, long)]
-    alpha: f64,
+    /// largest vertex index accepted from numeric input; larger ones are 
+    /// errors, or are skipped with --lenient
+    #[arg(long, value_name="n")]
+    max_index: Option<usize>,
+
+    /// the dumping factor; may be repeated to calculate the pagerank for 
+    /// each value, reading the graph only once
+    #[arg(short, long, required_unless_present="config")]
+    alpha: Vec<PrFloat>,
+
+    /// the teleport probability, if not 1 - alpha
+    #[arg(long, value_name="beta")]
+    teleport: Option<PrFloat>,
 
     /// the convergence criterion
-    #[arg(short, long)]
-    convergence: f64,
+    #[arg(short, long, required_unless_present="config")]
+    convergence: Option<PrFloat>,
 
-    /// hint for internal tables
-    #[arg(short, long)]
-    size: usize,
+    /// norm of the difference between iterations checked against the 
+    /// convergence criterion
+    #[arg(long, value_enum, default_value_t=Norm::L1)]
+    norm: Norm,
 
-    /// delimiter for separating vertex names in each input line
-    #[arg(short, long)]
-    delim: String,
+    /// hint for internal tables; 0 counts the vertices of the graph file first
+    #[arg(short, long, required_unless_present="config")]
+    size: Option<usize>,
+
+    /// count the vertices of the graph file before reading it, instead of 
+    /// relying on the size hint
+    #[arg(long)]
+    count_first: bool,
+
+    /// delimiter for separating vertex names in each input line; repeat it
+    /// to accept any of several, splitting each line at the first found
+    #[arg(short, long, required_unless_present_any=["tsv", "csv", "whitespace", "config"])]
+    delim: Vec<String>,
+
+    /// read tab-separated input; the same as a tab delimiter
+    #[arg(long, conflicts_with_all=["delim", "csv"])]
+    tsv: bool,
+
+    /// read comma-separated input, removing the double quotes around vertex 
+    /// names
+    #[arg(long, conflicts_with="delim")]
+    csv: bool,
+
+    /// separate the vertex names in each input line by any run of spaces 
+    /// and tabs
+    #[arg(long, conflicts_with_all=["delim", "tsv", "csv"])]
+    whitespace: bool,
 
     /// maximum number of iterations to perform
-    #[arg(short, long)]
-    max_iterations: usize,
+    #[arg(short, long, required_unless_present="config")]
+    max_iterations: Option<usize>,
+
+    /// perform exactly n iterations, without checking for convergence
+    #[arg(long, value_name="n")]
+    fixed_iterations: Option<usize>,
+
+    /// stop iterating after ms milliseconds, even without convergence
+    #[arg(long, value_name="ms")]
+    max_time_ms: Option<u64>,
+
+    /// write the state of the calculation to path when it ends, and every
+    /// --checkpoint-every iterations, so that it can be resumed
+    #[arg(long, value_name="path")]
+    checkpoint: Option<PathBuf>,
+
+    /// number of iterations between checkpoints; 0 writes only the last one
+    #[arg(long, value_name="n", default_value_t=0, requires="checkpoint")]
+    checkpoint_every: usize,
+
+    /// continue the calculation from a checkpoint written for the same graph
+    #[arg(long, value_name="path")]
+    resume: Option<PathBuf>,
+
+    /// graph_file; `-` reads the graph from the standard input. May be 
+    /// repeated to read the union of the graphs in several files
+    #[arg(short, long, value_name="graph_file", required_unless_present="stdin")]
+    file: Vec<PathBuf>,
+
+    /// file with the names of numeric vertices: an index, the delimiter and
+    /// a name per line
+    #[arg(long, value_name="path")]
+    labels: Option<PathBuf>,
+
+    /// file with the seed nodes of personalized pagerank: a node, the 
+    /// delimiter and its weight per line
+    #[arg(long, value_name="path")]
+    seed_file: Option<PathBuf>,
+
+    /// file with the only vertices to read, one per line: edges with any 
+    /// other vertex are skipped
+    #[arg(long, value_name="path")]
+    whitelist: Option<PathBuf>,
+
+    /// file with vertices to leave out, one per line: edges with any of 
+    /// them are skipped
+    #[arg(long, value_name="path", conflicts_with="whitelist")]
+    blacklist: Option<PathBuf>,
+
+    /// read the graph from the standard input
+    #[arg(long, conflicts_with="file")]
+    stdin: bool,
+
+    /// read an optional third column with the (non-negative) weight of each edge
+    #[arg(long)]
+    weighted: bool,
+
+    /// prefix of the comment lines of the graph file; empty for none
+    #[arg(long, value_name="prefix", default_value=pagerank::DEFAULT_COMMENT_PREFIX)]
+    comment_prefix: String,
+
+    /// number of header lines at the start of the graph file to ignore
+    #[arg(long, value_name="n", default_value_t=0)]
+    skip_header: usize,
+
+    /// treat lines without the delimiter as errors instead of skipping them
+    #[arg(long)]
+    strict: bool,
+
+    /// skip lines with invalid numeric vertices instead of stopping with an
+    /// error
+    #[arg(long, conflicts_with="strict")]
+    lenient: bool,
+
+    /// treat the graph as undirected, adding each edge in both directions
+    #[arg(long)]
+    undirected: bool,
+
+    /// reverse each edge, calculating the pagerank of the transposed graph
+    #[arg(long)]
+    reverse: bool,
+
+    /// skip each edge whose reverse was read before, keeping one direction
+    /// of edges listed in both, or with --undirected, both directions once
+    #[arg(long)]
+    dedup_symmetric: bool,
+
+    /// treat vertex names that differ only in case as the same vertex
+    #[arg(long)]
+    ignore_case: bool,
+
+    /// share the teleport only among the vertices with links, leaving out 
+    /// isolated ones
+    #[arg(long)]
+    ignore_isolated: bool,
+
+    /// count repeated edges instead of ignoring them
+    #[arg(long)]
+    multi_edges: bool,
+
+    /// read the input as a Matrix Market file, whatever its extension 
+    /// (files ending in .mtx are always read as such)
+    #[arg(long)]
+    mtx: bool,
+
+    /// output only the k nodes with the highest pagerank
+    #[arg(long, value_name="k")]
+    top: Option<usize>,
 
-    /// graph_file
-    #[arg(short, long, value_name="graph_file")]
-    file: PathBuf,
+    /// file to write the results to, instead of the standard output
+    #[arg(short, long, value_name="path")]
+    output: Option<PathBuf>,
+
+    /// algorithm to calculate; HITS performs the maximum or fixed number of
+    /// iterations, and the eigenvector centrality stops at the convergence 
+    /// criterion; their results are output as text
+    #[arg(long, value_enum, default_value_t=Algorithm::Pagerank)]
+    algorithm: Algorithm,
+
+    /// output format of the results
+    #[arg(long, value_enum, default_value_t=Format::Text)]
+    format: Format,
+
+    /// number of decimal places of the pagerank values in the text output;
+    /// 0 outputs them with full precision
+    #[arg(long, value_name="digits", default_value_t=0)]
+    precision: usize,
+}
+
+/// The parameters that can be read from a configuration file.
+#[derive(Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(deny_unknown_fields))]
+struct Config {
+    alpha: Option<PrFloat>,
+    convergence: Option<PrFloat>,
+    max_iterations: Option<usize>,
+    numeric: Option<bool>,
+    delim: Option<String>,
+    size: Option<usize>,
+}
+
+/// Reads the configuration file at path, as JSON if its extension is 
+/// .json, or else as TOML.
+#[cfg(feature = "config")]
+fn read_config(path: &Path) -> Result<Config, String> {
+    let s = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&s).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&s).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(feature = "config"))]
+fn read_config(_path: &Path) -> Result<Config, String> {
+    Err("configuration files require the config feature".to_string())
 }
 
 fn main() {
-    let mut t = Table::new();
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    let verbosity = match (cli.t, cli.v) {
+        (true, _) | (_, 2..) => Verbosity::Trace,
+        (_, 1) => Verbosity::Debug,
+        _ => Verbosity::Info,
+    };
+    let level = match verbosity {
+        Verbosity::Trace => LevelFilter::Trace,
+        Verbosity::Debug => LevelFilter::Debug,
+        _ => LevelFilter::Warn,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .format_timestamp(None)
+        .format_target(false)
+        .init();
 
-    if cli.t {
-        t.set_trace(true);
+    let config = match &cli.config {
+        Some(path) => read_config(path).unwrap_or_else(|e| {
+            eprintln!("Error reading {}: {}", path.display(), e);
+            exit(1);
+        }),
+        None => Config::default(),
+    };
+    if cli.alpha.is_empty() {
+        cli.alpha.push(config.alpha.unwrap_or(pagerank::DEFAULT_ALPHA));
     }
+    let convergence = cli.convergence.or(config.convergence).unwrap_or(pagerank::DEFAULT_CONVERGENCE);
+    let max_iterations = cli.max_iterations
+        .or(config.max_iterations)
+        .unwrap_or(pagerank::DEFAULT_MAX_ITERATIONS);
+    let size = cli.size.or(config.size).unwrap_or(0);
+    let numeric = cli.n || config.numeric.unwrap_or(pagerank::DEFAULT_NUMERIC);
 
-    if cli.n {
-        t.set_numeric(true)
+    let delims = match (cli.delim.is_empty(), cli.tsv, cli.csv, config.delim) {
+        (false, _, _, _) => cli.delim,
+        (true, true, _, _) => vec!["\t".to_string()],
+        (true, _, true, _) => vec![",".to_string()],
+        (true, _, _, Some(d)) => vec![d],
+        // In whitespace mode the delimiter is not used
+        (true, _, _, None) => vec![pagerank::DEFAULT_DELIM.to_string()],
+    };
+    let delims: Vec<&str> = delims.iter().map(String::as_str).collect();
+
+    let mut builder = TableBuilder::new()
+        .verbosity(verbosity)
+        .quiet(cli.quiet)
+        .timing(cli.timing)
+        .numeric(numeric)
+        .max_index(cli.max_index)
+        .weighted(cli.weighted)
+        .allow_multi_edges(cli.multi_edges)
+        .undirected(cli.undirected)
+        .reverse(cli.reverse)
+        .dedup_symmetric(cli.dedup_symmetric)
+        .ignore_isolated(cli.ignore_isolated)
+        .convergence(convergence)
+        .convergence_norm(cli.norm.into())
+        .max_iterations(max_iterations)
+        .fixed_iterations(cli.fixed_iterations)
+        .time_budget(cli.max_time_ms.map(Duration::from_millis))
+        .delims(&delims)
+        .whitespace(cli.whitespace)
+        .trim_quotes(cli.csv)
+        .case_insensitive(cli.ignore_case)
+        .comment_prefix(&cli.comment_prefix)
+        .skip_header(cli.skip_header)
+        .strict(cli.strict)
+        .lenient(cli.lenient)
+        .count_first(cli.count_first || size == 0)
+        .output_precision(cli.precision);
+    if let Some(beta) = cli.teleport {
+        builder = builder.teleport(beta);
+    }
+    for &a in &cli.alpha {
+        builder = builder.alpha(a);
     }
+    let mut t = match builder.build() {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Invalid argument: {}", e);
+            exit(1);
+        }
+    };
 
-    let alpha = cli.alpha;
-    if alpha < 0.0 || alpha >= 1.0 {
-        eprintln!("Invalid alpha argument");
+    let sweep = cli.alpha.len() > 1;
+    if sweep && cli.format == Format::Json {
+        eprintln!("Invalid argument: several alpha values cannot be output as JSON");
         exit(1);
     }
-    t.set_alpha(alpha);
+    let other = cli.algorithm != Algorithm::Pagerank;
+    if other && (cli.format != Format::Text || cli.top.is_some()) {
+        eprintln!("Invalid argument: only pagerank can be output in other formats or for the top vertices");
+        exit(1);
+    }
+    if sweep && cli.resume.is_some() {
+        eprintln!("Invalid argument: several alpha values cannot be resumed from a checkpoint");
+        exit(1);
+    }
+
+    let mut output: Box<dyn Write> = match &cli.output {
+        // Nothing is output, so an existing file is left intact
+        _ if cli.validate => Box::new(io::sink()),
+        Some(path) => match File::create(path) {
+            Ok(f) => Box::new(BufWriter::new(f)),
+            Err(e) => {
+                eprintln!("Error creating {}: {}", path.display(), e);
+                exit(1);
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    };
 
-    let convergence = cli.convergence;
-    if convergence == 0.0 {
-        eprintln!("Invalid convergence argument");
+    if size > 0 {
+        t.set_num_rows(size);
+    }
+
+    let filter = match (&cli.whitelist, &cli.blacklist) {
+        (Some(path), _) => Some((path, t.load_node_whitelist(path))),
+        (_, Some(path)) => Some((path, t.load_node_blacklist(path))),
+        _ => None,
+    };
+    if let Some((path, Err(e))) = filter {
+        eprintln!("Error reading {}: {}", path.display(), e);
         exit(1);
     }
-    t.set_convergence(convergence);
 
-    let size = cli.size;
-    if size == 0 {
-        eprintln!("Invalid size argument");
+    let num_files = cli.file.len();
+    let files: Vec<PathBuf> = cli.file.into_iter().filter(|f| f.as_os_str() != "-").collect();
+    if files.len() < num_files && !files.is_empty() {
+        eprintln!("Invalid file argument: `-` cannot be combined with other files");
         exit(1);
     }
-    t.set_num_rows(size);
+    if cli.mtx && files.len() > 1 {
+        eprintln!("Invalid file argument: --mtx reads a single file");
+        exit(1);
+    }
+    let input = if files.is_empty() {
+        "standard input".to_string()
+    } else {
+        files.iter().map(|f| f.display().to_string()).collect::<Vec<_>>().join(", ")
+    };
+    
+    if !cli.quiet {
+        t.print_params();
+        println!("Reading input from {} ...", input);
+    }
 
-    let iterations = cli.max_iterations;
-    if iterations == 0 {
-        eprintln!("Invalid iterations argument");
+    let read = match (files.as_slice(), cli.mtx) {
+        ([], true) => t.read_mtx_stdin(),
+        ([], false) => t.read_stdin(),
+        ([f], true) => t.read_mtx_file(f),
+        (files, _) => t.read_files(files),
+    };
+    if let Err(e) = read {
+        eprintln!("Error reading {}: {}", input, e);
         exit(1);
     }
+    if let Some(time) = t.get_read_time() {
+        eprintln!("Reading took {:.3?}", time);
+    }
+    if let Some(path) = &cli.labels {
+        if let Err(e) = t.load_labels(path) {
+            eprintln!("Error reading {}: {}", path.display(), e);
+            exit(1);
+        }
+    }
+    if let Some(path) = &cli.seed_file {
+        if let Err(e) = t.load_personalization(path) {
+            eprintln!("Error reading {}: {}", path.display(), e);
+            exit(1);
+        }
+    }
+    // After the personalization, which the estimate depends on
+    if cli.stats && !cli.validate {
+        eprintln!("{} estimated_iterations = {}", t.summary(), estimate_iterations(&mut t));
+        let fraction = t.dangling_fraction();
+        if fraction > cli.dangling_warning {
+            eprintln!("Warning: {:.1}% of the vertices are dangling; the graph may be incomplete",
+                100.0 * fraction);
+        }
+    }
+    if t.get_skipped_lines() > 0 {
+        eprintln!("Warning: skipped {} lines without the delimiter", t.get_skipped_lines());
+    }
+    if t.get_symmetric_duplicates() > 0 {
+        eprintln!("Collapsed {} symmetric duplicate edges", t.get_symmetric_duplicates());
+    }
+    if t.get_invalid_lines() > 0 {
+        eprintln!("Warning: skipped {} lines with invalid vertices", t.get_invalid_lines());
+    }
+    if t.get_filtered_edges() > 0 {
+        eprintln!("Filtered out {} edges by the vertex list", t.get_filtered_edges());
+    }
+    if cli.validate {
+        // Invalid input that is not skipped has already stopped the reading
+        // with an error
+        println!("{}", t.summary());
+        println!("skipped_lines = {} invalid_lines = {} symmetric_duplicates = {} filtered_edges = {}",
+            t.get_skipped_lines(), t.get_invalid_lines(), t.get_symmetric_duplicates(),
+            t.get_filtered_edges());
+        return;
+    }
+
+    if cli.algorithm == Algorithm::Hits {
+        if !cli.quiet {
+            println!("Calculating HITS ...");
+        }
+        let (authority, hub) = t.hits(cli.fixed_iterations.unwrap_or(max_iterations));
+        if let Err(e) = write_hits(&t, &authority, &hub, &mut output) {
+            eprintln!("Error writing results: {}", e);
+            exit(1);
+        }
+        return;
+    }
+    if cli.algorithm == Algorithm::Eigenvector {
+        if !cli.quiet {
+            println!("Calculating eigenvector centrality ...");
+        }
+        let iterations = cli.fixed_iterations.unwrap_or(max_iterations);
+        let centrality = t.eigenvector_centrality(iterations, convergence);
+        if let Err(e) = write_scores(&t, &centrality, &mut output) {
+            eprintln!("Error writing results: {}", e);
+            exit(1);
+        }
+        return;
+    }
 
-    let delim = cli.delim;
-    t.set_delim(&delim);
+    if let (Some(path), n) = (&cli.checkpoint, cli.checkpoint_every) {
+        if n > 0 {
+            let path = path.clone();
+            t.set_iteration_callback(Box::new(move |iterations, diff, pr| {
+                if iterations % n == 0 {
+                    let saved = write_checkpoint_file(&path, |w| {
+                        Table::write_checkpoint(w, iterations, diff, pr)
+                    });
+                    if let Err(e) = saved {
+                        eprintln!("Warning: error writing checkpoint {}: {}", path.display(), e);
+                    }
+                }
+            }));
+        }
+    }
 
-    let file = cli.file;
-    
-    t.print_params();
-    println!("Reading input from {} ...", file.display());
+    for &a in &cli.alpha {
+        // The alpha values were validated when the table was built
+        t.set_alpha(a).expect("valid alpha");
+        t.reset_results();
+        if let Some(path) = &cli.resume {
+            let resumed = File::open(path)
+                .map_err(PagerankError::from)
+                .and_then(|mut f| t.resume_from(&mut f));
+            if let Err(e) = resumed {
+                eprintln!("Error reading {}: {}", path.display(), e);
+                exit(1);
+            }
+        }
 
-    t.read_file(&file).unwrap();
+        if !cli.quiet {
+            println!("Calculating pagerank ...");
+        }
+        let result = match t.pagerank() {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Error calculating pagerank: {}", e);
+                exit(1);
+            }
+        };
+        if !cli.quiet {
+            println!("Done calculating after {} iterations!", result.iterations);
+        }
+        if let Some(time) = t.get_pagerank_time() {
+            eprintln!("Calculating took {:.3?}", time);
+        }
+        if let Some(path) = &cli.checkpoint {
+            if let Err(e) = write_checkpoint_file(path, |w| t.save_checkpoint(w)) {
+                eprintln!("Error writing checkpoint {}: {}", path.display(), e);
+                exit(1);
+            }
+        }
+        if result.timed_out {
+            eprintln!("WARNING: stopped by the time limit after {} iterations (diff={})",
+                result.iterations, result.final_diff);
+        } else if !result.converged && cli.fixed_iterations.is_none() {
+            eprintln!("WARNING: did not converge after {} iterations (diff={})",
+                result.iterations, result.final_diff);
+        }
+        let header = if sweep { write_header(cli.format, a, &mut output) } else { Ok(()) };
+        if let Err(e) = header.and_then(|_| write_results(&t, cli.format, cli.top, &mut output)) {
+            eprintln!("Error writing results: {}", e);
+            exit(1);
+        }
+    }
 
-    println!("Calculating pagerank ...");
-    t.pagerank();
-    println!("Done calculating!");
-    t.print_pagerank_v();
+}
 
+/// Estimates the number of iterations of the calculation from the ratio of
+/// the differences of a few probe iterations, whose results are then 
+/// discarded.
+fn estimate_iterations(t: &mut Table) -> usize {
+    let fixed = t.get_fixed_iterations();
+    let record = t.get_record_history();
+    t.set_fixed_iterations(Some(PROBE_ITERATIONS));
+    t.set_record_history(true);
+    let _ = t.pagerank();
+    let estimate = t.estimated_iterations();
+    t.set_fixed_iterations(fixed);
+    t.set_record_history(record);
+    t.reset_results();
+    estimate
+}
+
+/// Writes a checkpoint to path with f. The checkpoint is written to a 
+/// temporary file next to it first, which then replaces it, so that an 
+/// interruption leaves the previous checkpoint intact.
+fn write_checkpoint_file<F>(path: &Path, f: F) -> io::Result<()>
+    where F: FnOnce(&mut BufWriter<File>) -> io::Result<()>
+{
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut w = BufWriter::new(File::create(&tmp)?);
+    f(&mut w)?;
+    w.flush()?;
+    drop(w);
+    fs::rename(&tmp, path)
+}
+
+/// Writes a line that labels the results for the damping factor a, when 
+/// the pagerank is calculated for several of them.
+fn write_header<W: Write>(format: Format, a: PrFloat, w: &mut W) -> io::Result<()> {
+    match format {
+        Format::Dot => writeln!(w, "// alpha = {}", a),
+        _ => writeln!(w, "alpha = {}", a),
+    }
+}
+
+/// Writes the HITS scores of the vertices to w, as lines of 
+/// <node> = <authority> <hub>.
+fn write_hits<W: Write>(
+    t: &Table,
+    authority: &[PrFloat],
+    hub: &[PrFloat],
+    w: &mut W,
+) -> io::Result<()> {
+    for (i, (a, h)) in authority.iter().zip(hub).enumerate() {
+        writeln!(w, "{} = {} {}", t.get_node_name(i), t.format_rank(*a), t.format_rank(*h))?;
+    }
+    w.flush()
+}
+
+/// Writes the scores of the vertices to w, as lines of <node> = <score>.
+fn write_scores<W: Write>(t: &Table, scores: &[PrFloat], w: &mut W) -> io::Result<()> {
+    for (i, x) in scores.iter().enumerate() {
+        writeln!(w, "{} = {}", t.get_node_name(i), t.format_rank(*x))?;
+    }
+    w.flush()
+}
+
+/// Writes the results of the calculation to w in the given format, only 
+/// for the top k nodes if k is given.
+fn write_results<W: Write>(
+    t: &Table,
+    format: Format,
+    top: Option<usize>,
+    w: &mut W,
+) -> io::Result<()> {
+    match (format, top) {
+        (Format::Json, top) => t.write_pagerank_json(w, top)?,
+        (Format::Dot, _) => t.write_dot(w)?,
+        (Format::Text, Some(k)) => {
+            for (node, rank) in t.top_k(k) {
+                writeln!(w, "{} = {}", node, t.format_rank(rank))?;
+            }
+        }
+        (Format::Text, None) => t.write_pagerank_v(w)?,
+    }
+    w.flush()
 }
\ No newline at end of file
@@ -0,0 +1,44 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::table::PrFloat;
+
+/// The norm of the difference between two successive pagerank vectors that
+/// is checked against the convergence criterion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConvergenceNorm {
+    /// The sum of the absolute differences of the elements.
+    #[default]
+    L1,
+    /// The Euclidean length of the difference.
+    L2,
+    /// The largest absolute difference of any element.
+    LInf,
+}
+
+impl ConvergenceNorm {
+    /// Returns the contribution of the difference d of a single element.
+    pub(crate) fn term(self, d: PrFloat) -> PrFloat {
+        match self {
+            ConvergenceNorm::L1 | ConvergenceNorm::LInf => d.abs(),
+            ConvergenceNorm::L2 => d * d,
+        }
+    }
+
+    /// Combines two partial results of the norm.
+    pub(crate) fn combine(self, a: PrFloat, b: PrFloat) -> PrFloat {
+        match self {
+            ConvergenceNorm::L1 | ConvergenceNorm::L2 => a + b,
+            ConvergenceNorm::LInf => a.max(b),
+        }
+    }
+
+    /// Returns the norm from the combination of all the terms.
+    pub(crate) fn finish(self, acc: PrFloat) -> PrFloat {
+        match self {
+            ConvergenceNorm::L1 | ConvergenceNorm::LInf => acc,
+            ConvergenceNorm::L2 => acc.sqrt(),
+        }
+    }
+}
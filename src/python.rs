@@ -0,0 +1,90 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use pyo3::{
+    exceptions::{PyIOError, PyValueError},
+    prelude::*,
+};
+
+use crate::{
+    builder::TableBuilder,
+    error::PagerankError,
+    table::{PrFloat, Table, DEFAULT_ALPHA, DEFAULT_CONVERGENCE, DEFAULT_MAX_ITERATIONS},
+};
+
+/// Converts an error of the table to a Python exception: an IOError for I/O
+/// errors, and a ValueError for invalid input or parameters.
+fn to_py_err(e: PagerankError) -> PyErr {
+    match e {
+        PagerankError::Io(e) => PyIOError::new_err(e.to_string()),
+        e => PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// A Python wrapper around a [`Table`]. The table keeps callbacks that are
+/// not Send, so it can only be used from the thread that created it.
+#[pyclass(name = "Table", unsendable)]
+pub struct PyTable {
+    table: Table,
+}
+
+#[pymethods]
+impl PyTable {
+    /// Creates a table with the given parameters; the progress of reading
+    /// is not printed.
+    #[new]
+    #[pyo3(signature = (
+        numeric = false,
+        delim = None,
+        alpha = DEFAULT_ALPHA,
+        convergence = DEFAULT_CONVERGENCE,
+        max_iterations = DEFAULT_MAX_ITERATIONS,
+    ))]
+    fn new(
+        numeric: bool,
+        delim: Option<&str>,
+        alpha: PrFloat,
+        convergence: PrFloat,
+        max_iterations: usize,
+    ) -> PyResult<Self> {
+        let mut builder = TableBuilder::new()
+            .quiet(true)
+            .numeric(numeric)
+            .alpha(alpha)
+            .convergence(convergence)
+            .max_iterations(max_iterations);
+        if let Some(d) = delim {
+            builder = builder.delim(d);
+        }
+        let table = builder.build().map_err(to_py_err)?;
+        Ok(PyTable { table })
+    }
+
+    /// Adds an edge between the named nodes.
+    fn add_edge(&mut self, from: &str, to: &str) {
+        self.table.add_edge(from, to);
+    }
+
+    /// Reads the graph described in the file, replacing the one read before.
+    fn read_file(&mut self, path: PathBuf) -> PyResult<()> {
+        self.table.read_file(&path).map_err(to_py_err)
+    }
+
+    /// Calculates the pagerank and returns it as a dict from node names to
+    /// pagerank values. Raises a ValueError if the graph is empty.
+    fn pagerank(&mut self) -> PyResult<HashMap<String, PrFloat>> {
+        self.table.pagerank().map_err(to_py_err)?;
+        Ok(self.table.pagerank_map())
+    }
+
+    /// Returns the k nodes with the highest pagerank as a list of (name,
+    /// value) tuples, highest first.
+    fn top_k(&self, k: usize) -> Vec<(String, PrFloat)> {
+        self.table.top_k(k)
+    }
+}
+
+/// The Python module, named after the library.
+#[pymodule]
+fn pagerank(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyTable>()
+}
@@ -0,0 +1,21 @@
+use sprs::CsMat;
+
+use crate::table::{PrFloat, Table};
+
+impl Table {
+    /// Returns the transition matrix of the graph, the H matrix of the 
+    /// pagerank iteration, as a compressed sparse row matrix: the element 
+    /// at row i and column j is the probability of following a link from j
+    /// to i, one divided by the number of outgoing links of j, or in 
+    /// weighted or multi-edge mode, the weight of the link divided by the 
+    /// total weight of the links of j. The columns of dangling nodes are 
+    /// all zero, so the matrix is column-stochastic except for them, and 
+    /// the share of a redistributed self-loop is missing from the column of
+    /// its node; the iteration handles both through the A x I term rather 
+    /// than the matrix.
+    pub fn transition_matrix(&self) -> CsMat<PrFloat> {
+        let n = self.get_num_rows();
+        let (offsets, sources, values) = self.h_matrix().into_parts();
+        CsMat::new((n, n), offsets, sources, values)
+    }
+}
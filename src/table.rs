@@ -1,65 +1,421 @@
-use std::{collections::HashMap, process::exit, fs::File, io::{self, BufReader, BufRead}, path::PathBuf};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    iter,
+    mem,
+    path::{Path, PathBuf},
+    slice,
+    time::{Duration, Instant},
+};
 
-const DEFAULT_ALPHA: f64 = 0.85;
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+use log::{debug, log_enabled, trace, warn, Level, LevelFilter};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checkpoint::{self, Checkpoint},
+    csr::Csr,
+    dot,
+    error::PagerankError,
+    json,
+    norm::ConvergenceNorm,
+};
+
+/// The floating point type of the pagerank values and of the calculation: 
+/// f64, or with the f32 feature, f32, which halves the memory used by the 
+/// pagerank vectors and the hyperlink matrix at the cost of precision.
+#[cfg(not(feature = "f32"))]
+pub type PrFloat = f64;
+#[cfg(feature = "f32")]
+pub type PrFloat = f32;
+
+pub const DEFAULT_ALPHA: PrFloat = 0.85;
 // convergence 收敛性
-const DEFAULT_CONVERGENCE: f64 = 0.00001;
-const DEFAULT_MAX_ITERATIONS: usize = 10000;
-const DEFAULT_NUMERIC: bool = false;
-const DEFAULT_DELIM: &str = " => ";
+pub const DEFAULT_CONVERGENCE: PrFloat = 0.00001;
+pub const DEFAULT_MAX_ITERATIONS: usize = 10000;
+pub const DEFAULT_NUMERIC: bool = false;
+pub const DEFAULT_DELIM: &str = " => ";
+pub const DEFAULT_COMMENT_PREFIX: &str = "#";
+pub const DEFAULT_PROGRESS_INTERVAL: usize = 100000;
+
+/// How far the sum of a valid pagerank vector may be from one.
+#[cfg(not(feature = "f32"))]
+const SUM_TOLERANCE: PrFloat = 1e-6;
+#[cfg(feature = "f32")]
+const SUM_TOLERANCE: PrFloat = 1e-3;
+
+/// The outcome of a pagerank calculation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PagerankResult {
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// Whether the final difference satisfied the convergence criterion.
+    pub converged: bool,
+    /// The difference between the last two pagerank vectors.
+    pub final_diff: PrFloat,
+    /// Whether the calculation stopped because it exceeded the time budget,
+    /// in which case it did not converge.
+    pub timed_out: bool,
+}
+
+/// A summary of the graph of a table, as returned by Table::summary().
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphStats {
+    /// The number of distinct nodes, as returned by num_nodes().
+    pub nodes: usize,
+    /// The number of edges, as returned by num_edges().
+    pub edges: usize,
+    /// The number of dangling nodes, as returned by dangling_nodes().
+    pub dangling: usize,
+    /// The highest number of incoming links of a node.
+    pub max_in_degree: usize,
+    /// The highest number of outgoing links of a node.
+    pub max_out_degree: usize,
+    /// The average number of outgoing (equivalently, incoming) edges of a 
+    /// node.
+    pub average_degree: f64,
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nodes = {} edges = {} dangling = {} max_in_degree = {} max_out_degree = {} average_degree = {}",
+            self.nodes, self.edges, self.dangling, self.max_in_degree, self.max_out_degree, 
+            self.average_degree)
+    }
+}
+
+/// The order in which nodes are sorted by pagerank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Lowest pagerank first.
+    Ascending,
+    /// Highest pagerank first.
+    Descending,
+}
+
+/// How much a table reports about its work. The messages of the debug and
+/// trace levels go through the log crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Verbosity {
+    /// Nothing is reported, not even the progress of reading.
+    Silent,
+    /// The progress of reading is printed, unless quiet.
+    #[default]
+    Info,
+    /// The difference of each pagerank iteration is logged as well.
+    Debug,
+    /// Everything is logged, including the H matrix and the pagerank vector
+    /// after each iteration.
+    Trace,
+}
+
+/// How arcs from a node to itself are handled when they are added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SelfLoopPolicy {
+    /// Self-loops are arcs like any other, so a node passes part of its 
+    /// pagerank to itself.
+    #[default]
+    Keep,
+    /// Self-loops are ignored.
+    Drop,
+    /// Self-loops count as outgoing links of their node, but the pagerank 
+    /// they carry is distributed to all nodes, as for dangling nodes.
+    Redistribute,
+}
+
+/// How the pagerank of dangling nodes, which have no outgoing links, is 
+/// distributed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DanglingPolicy {
+    /// Distributed uniformly to all nodes.
+    #[default]
+    Uniform,
+    /// Distributed like teleportation, by the personalization vector if 
+    /// one is set, otherwise uniformly.
+    Teleport,
+    /// Lost; the pagerank vector is renormalized after each iteration so 
+    /// that it still sums to one.
+    Ignore,
+}
+
+/// The method used to calculate each pagerank iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IterationMethod {
+    /// Each new pagerank vector is calculated from the previous one only.
+    #[default]
+    Jacobi,
+    /// The pagerank vector is updated in place, each row using the values 
+    /// of the rows already updated in the same iteration. It converges to the 
+    /// same pagerank, but the number of iterations and the intermediate 
+    /// vectors depend on the order of the nodes. The rows are 
+    /// always calculated serially.
+    GaussSeidel,
+    /// Like Jacobi, but instead of summing the contributions of the sources
+    /// of each row, the pagerank of each node is scattered along its 
+    /// outgoing links, which are derived from the rows. It calculates the 
+    /// same vectors; the nodes are always processed serially.
+    Push,
+}
+
+/// A function called at the end of each pagerank iteration with the number 
+/// of the iteration (starting from one), the difference from the previous
+/// pagerank vector, and the current pagerank vector.
+pub type IterationCallback = Box<dyn FnMut(usize, PrFloat, &[PrFloat])>;
+
+/// A function called while a graph is read, every progress interval lines 
+/// and at the end of each input, with the number of lines read so far from
+/// the input and the number of vertices of the graph.
+pub type ProgressCallback = Box<dyn FnMut(usize, usize)>;
+
+/// The terms that turn an element of the H multiplication, h, into an 
+/// element of the next pagerank vector: (alpha * h + A x I + 1 x I) * scale.
+struct Terms<'a> {
+    alpha: PrFloat,
+    one_av: PrFloat,  // an element of the A x I vector
+    one_iv: PrFloat,  // an element of the 1 x I vector, or its total with personalization
+    personalization: Option<&'a [PrFloat]>,  // the weights of the 1 x I vector
+    live: Option<&'a [bool]>,  // the nodes that get the uniform terms, if not all
+    scale: PrFloat,  // the factor that renormalizes the vector
+}
+
+impl Terms<'_> {
+    /// Returns the element of the next pagerank vector for row i, whose 
+    /// element of the H multiplication is h. Without a personalization 
+    /// vector the elements of the 1 x I vector are identical for the nodes 
+    /// that share it; otherwise one_iv is the total teleport mass, weighted 
+    /// by the personalization.
+    fn element(&self, h: PrFloat, i: usize) -> PrFloat {
+        let shared = self.live.is_none_or(|live| live[i]);
+        let av = if shared { self.one_av } else { 0.0 };
+        let iv = match self.personalization {
+            Some(p) => self.one_iv * p[i],
+            None if shared => self.one_iv,
+            None => 0.0,
+        };
+        (h * self.alpha + av + iv) * self.scale
+    }
+}
+
+/// A subgraph induced by a subset of the nodes of a table, renumbered in
+/// order.
+struct Induced {
+    rows: Vec<Vec<usize>>,
+    weights: Vec<Vec<f64>>,
+    loop_weights: Vec<Option<f64>>,
+    names: Vec<(usize, String)>,  // the names of the nodes that have one
+    personalization: Option<HashMap<usize, f64>>,
+}
+
+/// The nodes of a whitelist or blacklist, which decide the edges that are 
+/// read.
+struct NodeFilter {
+    names: HashSet<String>,  // the keys of the listed names, for string input
+    indices: HashSet<usize>,  // the listed indices, for numeric input
+    whitelist: bool,  // only edges between listed nodes are kept, instead of only edges without any
+}
+
+impl NodeFilter {
+    /// Returns true if an edge between nodes of which listed tells whether 
+    /// each is in the list is kept.
+    fn keeps(&self, from_listed: bool, to_listed: bool) -> bool {
+        if self.whitelist {
+            from_listed && to_listed
+        } else {
+            !from_listed && !to_listed
+        }
+    }
+}
 
 /// A PageRank calculator. It is responsible for reading data, performing 
 /// the algorithmic calculations, and outputing the results.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Table {
-    trace: bool,  // enabling tracing output
-    alpha: f64,  // the pagerank damping factor 阻尼系数
-    convergence: f64,
+    verbosity: Verbosity,
+    quiet: bool,  // no progress output while reading
+    timing: bool,  // the durations of reading and of the calculation are measured
+    progress_interval: usize,  // number of lines between progress reports, zero for none
+    alpha: PrFloat,  // the pagerank damping factor 阻尼系数
+    teleport: Option<PrFloat>,  // the teleport probability, if not 1 - alpha
+    convergence: PrFloat,
+    convergence_norm: ConvergenceNorm,
     max_iterations: usize,
+    fixed_iterations: Option<usize>,  // exact number of iterations, without a convergence check
+    time_budget: Option<Duration>,  // the time after which the iterations stop
     delim: String,
+    extra_delims: Vec<String>,  // other delimiters; a line is split at the first one found
+    whitespace: bool,  // fields are separated by runs of whitespace instead of the delimiter
+    trim_quotes: bool,  // double quotes around the fields of the input are removed
+    case_insensitive: bool,  // node names differing only in case are the same node
+    comment_prefix: String,  // lines starting with the prefix are comments, unless it is empty
+    skip_header: usize,  // number of header lines at the start of each input
+    strict: bool,  // lines without the delimiter are errors instead of being skipped
+    lenient: bool,  // lines with invalid numeric vertices are skipped instead of being errors
+    count_first: bool,  // files are scanned to count the vertices before they are read
     numeric: bool,  // input graph has numeric, zero-based indexed vertices
+    max_index: Option<usize>,  // the largest numeric vertex accepted from the input
     num_outgoing: Vec<usize>,  // number of outgoing links per column
     rows: Vec<Vec<usize>>,  // the rowns of the hyperlink matrix
+    weighted: bool,  // edges carry weights
+    allow_multi_edges: bool,  // repeated edges add to the weight of the link
+    undirected: bool,  // each edge adds the reverse arc as well
+    reverse: bool,  // each edge adds the arc from its target to its source instead
+    dedup_symmetric: bool,  // an edge read after its reverse is skipped
+    symmetric_duplicates: usize,  // edges skipped by the last read as the reverse of another
+    filtered_edges: usize,  // edges skipped by the last read because of the node filter
+    self_loops: SelfLoopPolicy,
+    dangling_policy: DanglingPolicy,
+    ignore_isolated: bool,  // isolated nodes do not share the uniform teleport
+    iteration_method: IterationMethod,
+    output_precision: usize,  // decimal places of the text output, zero for full precision
+    loop_weights: Vec<Option<f64>>,  // the weight of the redistributed self-loop per node, if any
+    num_repeated: usize,  // repeated edges counted in multi-edge mode
+    weights: Vec<Vec<f64>>,  // the weights of the links in rows, in weighted or multi-edge mode
+    out_weights: Vec<f64>,  // total weight of outgoing links per column, in weighted or multi-edge mode
     nodes_to_idx: HashMap<String, usize>,  // mapping from string node IDs to numeric
     idx_to_nodes: HashMap<usize, String>,  // mapping from numeric node IDs to string
-    pr: Vec<f64>,  // the pagerank table
+    skipped_lines: usize,  // lines without the delimiter skipped by the last read
+    invalid_lines: usize,  // lines with invalid numeric vertices skipped by the last read
+    pr: Vec<PrFloat>,  // the pagerank table
+    last_result: Option<PagerankResult>,  // the outcome of the last calculation
+    slowest_node: Option<usize>,  // the node that changed most in the last iteration
+    record_history: bool,  // the difference of each iteration is recorded
+    warm_start: bool,  // the calculation starts from the pagerank of the last one
+    history: Vec<PrFloat>,  // the differences of the iterations of the last calculation
+    personalization: Option<HashMap<usize, f64>>,  // normalized teleport weights per node
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read_edges: HashSet<(usize, usize)>,  // the edges read so far, to skip their reverses
+    #[cfg_attr(feature = "serde", serde(skip))]
+    node_filter: Option<NodeFilter>,  // the whitelist or blacklist applied while reading
+    #[cfg_attr(feature = "serde", serde(skip))]
+    resume: Option<Checkpoint>,  // the state the next calculation continues from
+    #[cfg_attr(feature = "serde", serde(skip))]
+    iteration_callback: Option<IterationCallback>,  // called at the end of each iteration
+    #[cfg_attr(feature = "serde", serde(skip))]
+    progress_callback: Option<ProgressCallback>,  // called instead of printing the progress
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read_start: Option<Instant>,  // when the read in progress started, with timing
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read_time: Option<Duration>,  // the duration of the last read, with timing
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pagerank_time: Option<Duration>,  // the duration of the last calculation, with timing
 }
 
 impl Default for Table {
     fn default() -> Self {
         Self { 
-            trace: false, 
+            verbosity: Verbosity::Info,
+            quiet: false,
+            timing: false,
+            progress_interval: DEFAULT_PROGRESS_INTERVAL,
             alpha: DEFAULT_ALPHA, 
+            teleport: None,
             convergence: DEFAULT_CONVERGENCE, 
+            convergence_norm: ConvergenceNorm::L1,
             max_iterations: DEFAULT_MAX_ITERATIONS, 
+            fixed_iterations: None,
+            time_budget: None,
             delim: DEFAULT_DELIM.to_string(), 
+            extra_delims: Vec::new(),
+            whitespace: false,
+            trim_quotes: false,
+            case_insensitive: false,
+            comment_prefix: DEFAULT_COMMENT_PREFIX.to_string(),
+            skip_header: 0,
+            strict: false,
+            lenient: false,
+            count_first: false,
             numeric: DEFAULT_NUMERIC, 
+            max_index: None,
             num_outgoing: Vec::new(), 
             rows: Vec::new(), 
+            weighted: false,
+            allow_multi_edges: false,
+            undirected: false,
+            reverse: false,
+            dedup_symmetric: false,
+            symmetric_duplicates: 0,
+            filtered_edges: 0,
+            self_loops: SelfLoopPolicy::Keep,
+            dangling_policy: DanglingPolicy::Uniform,
+            ignore_isolated: false,
+            iteration_method: IterationMethod::Jacobi,
+            output_precision: 0,
+            loop_weights: Vec::new(),
+            num_repeated: 0,
+            weights: Vec::new(),
+            out_weights: Vec::new(),
             nodes_to_idx: HashMap::new(), 
             idx_to_nodes: HashMap::new(), 
+            skipped_lines: 0,
+            invalid_lines: 0,
             pr: Vec::new(), 
+            last_result: None,
+            slowest_node: None,
+            record_history: false,
+            warm_start: false,
+            history: Vec::new(),
+            personalization: None,
+            read_edges: HashSet::new(),
+            node_filter: None,
+            resume: None,
+            iteration_callback: None,
+            progress_callback: None,
+            read_start: None,
+            read_time: None,
+            pagerank_time: None,
         }
     }
 }
 
 impl Table {
-    fn insert_into_vector<T>(v: &mut Vec<T>, t: T) -> bool 
-        where T: PartialOrd
+    /// Inserts t into the sorted vector v, keeping it sorted and free of 
+    /// duplicates.
+    /// 
+    /// Returns Ok with the position where t was inserted, or Err with the 
+    /// position of the existing element if v already contains it.
+    fn insert_into_vector<T>(v: &mut Vec<T>, t: T) -> Result<usize, usize>
+        where T: Ord
     {
-        let mut i = 0;
-        // 除了加 .iter() 有更符合直觉的办法吗?
-        for item in v.iter() {
-            if *item > t {
-                break;
+        match v.binary_search(&t) {
+            Ok(i) => Err(i),
+            Err(i) => {
+                v.insert(i, t);
+                Ok(i)
             }
-            i += 1;
         }
-        
-        if i == v.len() {
-            v.push(t);
-            true
-        } else {
-            v.insert(i, t);
-            false
+    }
+
+    /// Returns true if the weights of the links are kept, which happens in 
+    /// weighted mode and when multi-edges are allowed.
+    fn keeps_weights(&self) -> bool {
+        self.weighted || self.allow_multi_edges
+    }
+
+    /// Brings the weights of the links in line with keeps_weights(), after 
+    /// a change of mode: the links of a table that did not keep weights 
+    /// before get a weight of one, and the weights are dropped when they 
+    /// are no longer needed.
+    fn update_weights(&mut self, kept_before: bool) {
+        if self.keeps_weights() && !kept_before {
+            self.weights = self.rows.iter().map(|r| vec![1.0; r.len()]).collect();
+            self.out_weights = self.num_outgoing.iter().map(|n| *n as f64).collect();
+        } else if !self.keeps_weights() {
+            self.weights.clear();
+            self.out_weights.clear();
         }
     }
 
@@ -68,9 +424,33 @@ impl Table {
     fn reset(&mut self) {
         self.num_outgoing.clear();
         self.rows.clear();
+        self.weights.clear();
+        self.out_weights.clear();
+        self.loop_weights.clear();
+        self.num_repeated = 0;
         self.nodes_to_idx.clear();
         self.idx_to_nodes.clear();
+        self.skipped_lines = 0;
+        self.invalid_lines = 0;
+        self.symmetric_duplicates = 0;
+        self.filtered_edges = 0;
+        self.read_edges.clear();
+        self.read_start = self.timing.then(Instant::now);
+        self.read_time = None;
+        self.reset_results();
+    }
+
+    /// Clears the results of the last pagerank calculation: the pagerank 
+    /// vector, its outcome and the convergence history. The graph is kept, 
+    /// so that the pagerank can be calculated again, e.g. with a different 
+    /// damping factor.
+    pub fn reset_results(&mut self) {
         self.pr.clear();
+        self.resume = None;
+        self.last_result = None;
+        self.slowest_node = None;
+        self.history.clear();
+        self.pagerank_time = None;
     }
 
     /// Adds a mapping from a node string ID (key) to a numeric one to the 
@@ -78,59 +458,175 @@ impl Table {
     /// 
     /// Returns the mapped value of the node; if the node has already 
     /// been mapped, the already mapped index.
-    fn insert_mapping(&mut self, key: String) -> usize {
-        match self.nodes_to_idx.get(&key) {
+    /// In case-insensitive mode the node keeps the name it was first 
+    /// mapped with.
+    pub(crate) fn insert_mapping(&mut self, key: String) -> usize {
+        match self.nodes_to_idx.get(self.node_key(&key).as_ref()) {
             Some(&index) => index,
             None => {
                 let idx = self.nodes_to_idx.len();
-                self.nodes_to_idx.insert(key.clone(), idx);
-                self.idx_to_nodes.insert(idx, key.clone());
+                self.nodes_to_idx.insert(self.node_key(&key).into_owned(), idx);
+                self.idx_to_nodes.insert(idx, key);
                 idx
             }
         }
     }
 
-    /// Adds an arc to the hyperlink matrix between from and to.
-    fn add_arc(&mut self, from: usize, to: usize) -> bool {
-        let mut ret = false;
-        let mut max_dim = if from > to {
-            from
+    /// Returns the key of the node name in the mapping from names to 
+    /// indices: the name itself, or in case-insensitive mode, its lowercase
+    /// form.
+    fn node_key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive {
+            Cow::Owned(name.to_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+
+    /// Adds an arc to the hyperlink matrix between from and to, or in 
+    /// reverse mode between to and from, and in undirected mode, unless it 
+    /// is a self-loop, the reverse arc as well.
+    /// 
+    /// Returns true if any of the arcs was added or counted.
+    fn add_arc(&mut self, from: usize, to: usize, weight: f64) -> bool {
+        let (from, to) = if self.reverse { (to, from) } else { (from, to) };
+        let added = self.add_directed_arc(from, to, weight);
+        if self.undirected && from != to {
+            self.add_directed_arc(to, from, weight) || added
+        } else {
+            added
+        }
+    }
+
+    /// Adds an arc to the hyperlink matrix between from and to. The weight
+    /// of the arc is only used in weighted mode; otherwise it is one. If 
+    /// the arc already exists, it is ignored, unless multi-edges are 
+    /// allowed, in which case its weight is added to that of the link. 
+    /// Self-loops are handled according to the self-loop policy.
+    /// 
+    /// Returns true if the arc was added or counted.
+    fn add_directed_arc(&mut self, from: usize, to: usize, weight: f64) -> bool {
+        let weight = if self.weighted { weight } else { 1.0 };
+        let max_dim = if from > to {
+            from + 1
         } else {
-            to
+            to + 1
         };
 
-        if self.trace {
-            println!("checking to add {} => {}", from, to);
+        trace!("checking to add {} => {}", from, to);
+
+        if self.rows.len() < max_dim {
+            trace!("resizing rows from {} to {}", self.rows.len(), max_dim);
+            self.rows.resize_with(max_dim, Vec::new);
+        }
+        if self.num_outgoing.len() < max_dim {
+            self.num_outgoing.resize(max_dim, 0);
+        }
+        if self.keeps_weights() {
+            if self.weights.len() < max_dim {
+                self.weights.resize_with(max_dim, Vec::new);
+            }
+            if self.out_weights.len() < max_dim {
+                self.out_weights.resize(max_dim, 0.0);
+            }
+        }
+
+        if from == to {
+            match self.self_loops {
+                SelfLoopPolicy::Keep => {}
+                SelfLoopPolicy::Drop => {
+                    trace!("dropped {} => {}", from, to);
+                    return false;
+                }
+                SelfLoopPolicy::Redistribute => return self.add_loop(from, weight),
+            }
         }
 
-        if self.rows.len() <= max_dim {
-            max_dim += 1;
-            if self.trace {
-                println!("resizing rows from {} to {}", self.rows.len(), max_dim);
+        match Self::insert_into_vector(&mut self.rows[to], from) {
+            Ok(i) => {
+                self.num_outgoing[from] += 1;
+                if self.keeps_weights() {
+                    self.weights[to].insert(i, weight);
+                    self.out_weights[from] += weight;
+                }
+                trace!("added {} => {}", from, to);
+                true
             }
-            
-            self.rows.resize_with(max_dim, || Vec::new());
-            if self.num_outgoing.len() <= max_dim {
-                self.num_outgoing.resize(max_dim, 0);
+            Err(i) if self.allow_multi_edges => {
+                self.weights[to][i] += weight;
+                self.out_weights[from] += weight;
+                self.num_repeated += 1;
+                trace!("counted {} => {} again", from, to);
+                true
             }
+            Err(_) => false,
+        }
+    }
+
+    /// Records a self-loop of node k that is redistributed: it counts as an
+    /// outgoing link of k, but it is not part of the rows.
+    fn add_loop(&mut self, k: usize, weight: f64) -> bool {
+        if self.loop_weights.len() <= k {
+            self.loop_weights.resize(k + 1, None);
         }
 
-        ret = Self::insert_into_vector(&mut self.rows[to], from);
-        
-        if ret {
-            self.num_outgoing[from] += 1;
-            if self.trace {
-                println!("added {} => {}", from, to);
+        match self.loop_weights[k] {
+            None => {
+                self.loop_weights[k] = Some(weight);
+                self.num_outgoing[k] += 1;
+                if self.keeps_weights() {
+                    self.out_weights[k] += weight;
+                }
+                trace!("added {} => {} for redistribution", k, k);
+                true
+            }
+            Some(w) if self.allow_multi_edges => {
+                self.loop_weights[k] = Some(w + weight);
+                self.out_weights[k] += weight;
+                self.num_repeated += 1;
+                trace!("counted {} => {} again for redistribution", k, k);
+                true
             }
+            Some(_) => false,
         }
+    }
 
-        ret
+    /// Returns the share of the pagerank of node k that goes through its 
+    /// redistributed self-loop.
+    fn loop_share(&self, k: usize) -> PrFloat {
+        match self.loop_weights.get(k) {
+            Some(Some(w)) if self.keeps_weights() => (w / self.out_weights[k]) as PrFloat,
+            Some(Some(_)) => 1.0 / self.num_outgoing[k] as PrFloat,
+            _ => 0.0,
+        }
     }
 
     pub fn new() -> Table {
         Default::default()
     }
 
+    /// Creates a numeric table with the given edges between (zero-based) 
+    /// node indices, ready for pagerank().
+    pub fn from_edges(edges: &[(usize, usize)]) -> Table {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        for &(from, to) in edges {
+            t.add_edge_idx(from, to);
+        }
+        t
+    }
+
+    /// Creates a table with the given edges between named nodes, ready for 
+    /// pagerank().
+    pub fn from_named_edges(edges: &[(&str, &str)]) -> Table {
+        let mut t = Table::new();
+        t.set_numeric(false);
+        for &(from, to) in edges {
+            t.add_edge(from, to);
+        }
+        t
+    }
+
     /// Reserves space for the internal tables used for the PageRank calculation.
     /// It is not necessory to call the method; space will be reserved as needed;
     /// however, if the size of the internal tables is known beforehand and is 
@@ -142,6 +638,10 @@ impl Table {
     pub fn reserve(&mut self, size: usize) {
         self.num_outgoing.reserve(size);
         self.rows.reserve(size);
+        if self.keeps_weights() {
+            self.weights.reserve(size);
+            self.out_weights.reserve(size);
+        }
     }
 
     /// Returns the number of rows of the link matrix.
@@ -149,322 +649,3947 @@ impl Table {
         self.rows.len()
     }
 
-    /// Sets the number of rows of the link matrix.
-    pub fn set_num_rows(&mut self, num_rows: usize) {
-        self.num_outgoing.resize(num_rows, 0);
-        self.rows.resize_with(num_rows, || { Vec::new() });
+    /// Returns the number of edges of the graph. Repeated edges are counted
+    /// once, unless multi-edges are allowed, and dropped self-loops are not 
+    /// counted.
+    pub fn num_edges(&self) -> usize {
+        self.num_outgoing.iter().sum::<usize>() + self.num_repeated
     }
 
-    /// Reads the graph described in filename.
-    pub fn read_file(&mut self, filename: &PathBuf) -> io::Result<i32> {
-        self.reset();
+    /// Returns the number of distinct nodes of the graph. For numeric input 
+    /// these are the indices with at least one link, which may be fewer than 
+    /// get_num_rows() if some indices are not used.
+    pub fn num_nodes(&self) -> usize {
+        (0..self.rows.len()).filter(|&k| self.is_node(k)).count()
+    }
 
-        let file = File::open(filename)?;
-        let infile = BufReader::new(file);
-        let delim_len = self.delim.len();
-        let mut linenum = 0;
-        for line_result in infile.lines() {
-            let line = line_result?;
-            let mut from: &str;
-            let mut to: &str;
-            let from_idx: usize;
-            let to_idx: usize;
-            let pos = line.find(self.delim.as_str());
-            
-            if pos.is_some() {
-                let pos = pos.unwrap();
-                from = &line[0..pos];
-                from = from.trim();
-                if !self.numeric {
-                    from_idx = self.insert_mapping(from.to_string());
-                } else {
-                    from_idx = from.parse().unwrap();
-                }
+    /// Returns true if the given index is one of the nodes counted by 
+    /// num_nodes(): for numeric input, an index with at least one link, and
+    /// otherwise, an index with a name.
+    fn is_node(&self, k: usize) -> bool {
+        if self.numeric {
+            self.num_outgoing[k] > 0 || !self.rows[k].is_empty()
+        } else {
+            self.idx_to_nodes.contains_key(&k)
+        }
+    }
 
-                to = &line[pos+delim_len..];
-                to = to.trim();
-                if !self.numeric {
-                    to_idx = self.insert_mapping(to.to_string());
-                } else {
-                    to_idx = to.parse().unwrap();
-                }
-                self.add_arc(from_idx, to_idx);
-            }
+    /// Returns the indices of the dangling nodes, which have no outgoing 
+    /// links, or, in weighted mode, only links with zero weight. Their 
+    /// pagerank is distributed to all nodes. Like num_nodes(), it leaves 
+    /// out the unused indices of numeric input.
+    pub fn dangling_nodes(&self) -> Vec<usize> {
+        (0..self.num_outgoing.len()).filter(|&k| self.is_node(k) && self.is_dangling(k)).collect()
+    }
 
-            linenum += 1;
-            if linenum != 0 && linenum % 100000 == 0 {
-                println!("read {} lines, {} vertices", linenum, self.rows.len());
-            }
+    /// Returns the fraction of the nodes counted by num_nodes() that are 
+    /// dangling, or zero for an empty graph. A large fraction, which usually
+    /// means that the graph is incomplete, e.g. a partial crawl, moves the 
+    /// pagerank towards the uniform distribution.
+    pub fn dangling_fraction(&self) -> f64 {
+        let nodes = self.num_nodes();
+        if nodes == 0 {
+            return 0.0;
         }
+        self.dangling_nodes().len() as f64 / nodes as f64
+    }
 
-        println!("read {} lines, {} vertices", linenum, self.rows.len());
+    /// Returns the names of the dangling nodes, as dangling_nodes() does for
+    /// their indices.
+    pub fn dangling_node_names(&self) -> Vec<String> {
+        self.dangling_nodes().into_iter().map(|k| self.get_node_name(k)).collect()
+    }
 
-        self.nodes_to_idx.clear();
-        self.reserve(self.idx_to_nodes.len());
+    /// Returns the names of the nodes that link to the given node, in the
+    /// order of their indices. The list is empty if the node is unknown.
+    pub fn in_neighbors(&self, node: &str) -> Vec<String> {
+        self.get_node_index(node)
+            .map(|idx| self.rows[idx].iter().map(|&k| self.get_node_name(k)).collect())
+            .unwrap_or_default()
+    }
 
-        Ok(0)
+    /// Returns an iterator over the links of the graph, as (from, to) pairs
+    /// of node indices, including redistributed self-loops. The links are 
+    /// ordered by target, and then by source, except for the redistributed
+    /// self-loops, which come last. Repeated edges are yielded once.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let links = self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(to, cr)| cr.iter().map(move |&from| (from, to)));
+        let loops = self.loop_weights
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.is_some())
+            .map(|(k, _)| (k, k));
+        links.chain(loops)
     }
 
-    /// Calculates the pagerank of the hyperlink matrix.
-    pub fn pagerank(&mut self) {
-        let mut diff: f64 = 1.0;
-        let mut sum_pr: f64;  // sum of current pagerank vector elements
-        let mut dangling_pr: f64;  // sum of current pagerank vector elements for dangling nodes
-        let mut num_iterations = 0;
-        let mut old_pr: Vec<f64> = Vec::new();
+    /// Returns the indices of the nodes that the given node links to, in 
+    /// increasing order, including itself for a redistributed self-loop. 
+    /// The list is empty if there is no such node. The links of all the 
+    /// nodes are searched, so to visit the graph it is faster to collect 
+    /// edges() once.
+    pub fn neighbors_out(&self, node: usize) -> Vec<usize> {
+        let has_loop = self.loop_weights.get(node).is_some_and(Option::is_some);
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(to, row)| row.binary_search(&node).is_ok() || (*to == node && has_loop))
+            .map(|(to, _)| to)
+            .collect()
+    }
 
-        let num_rows = self.rows.len();
+    /// Returns the number of outgoing links of the given node, or None if 
+    /// the node is unknown. Repeated edges are counted once.
+    pub fn out_degree(&self, node: &str) -> Option<usize> {
+        self.get_node_index(node).and_then(|idx| self.num_outgoing.get(idx).copied())
+    }
 
-        if num_rows == 0 {
-            return;
+    /// Returns a summary of the graph: its numbers of nodes, edges and 
+    /// dangling nodes, and the degrees of its nodes. It can be used to check
+    /// the graph that was read before calculating its pagerank.
+    pub fn summary(&self) -> GraphStats {
+        let nodes = self.num_nodes();
+        let edges = self.num_edges();
+        GraphStats {
+            nodes,
+            edges,
+            dangling: self.dangling_nodes().len(),
+            max_in_degree: self.rows.iter().map(Vec::len).max().unwrap_or(0),
+            max_out_degree: self.num_outgoing.iter().copied().max().unwrap_or(0),
+            average_degree: if nodes > 0 { edges as f64 / nodes as f64 } else { 0.0 },
         }
+    }
 
-        self.pr.resize(num_rows, 0.0);
+    /// Returns the number of incoming links of each node, by index. 
+    /// Repeated edges are counted once, and redistributed self-loops are not
+    /// counted.
+    pub fn in_degrees(&self) -> Vec<usize> {
+        self.rows.iter().map(Vec::len).collect()
+    }
 
-        self.pr[0] = 1.0;
+    /// Returns the number of outgoing links of each node, by index, as 
+    /// out_degree() does for a single node.
+    pub fn out_degrees(&self) -> Vec<usize> {
+        self.num_outgoing.clone()
+    }
 
-        if self.trace {
-            self.print_pagerank();
-        }
+    /// Returns the in-degree centrality of each node, by index: its number 
+    /// of incoming links divided by the number of other nodes, the most it
+    /// can have. It is a cheap baseline to compare the pagerank with.
+    pub fn in_degree_centrality(&self) -> Vec<PrFloat> {
+        let others = self.rows.len().saturating_sub(1).max(1) as PrFloat;
+        self.rows.iter().map(|r| r.len() as PrFloat / others).collect()
+    }
 
-        while diff > self.convergence && num_iterations < self.max_iterations {
-            sum_pr = 0.0;
-            dangling_pr = 0.0;
+    /// Calculates the HITS scores of the nodes, by index, with the given 
+    /// number of iterations of the mutual reinforcement of hubs and 
+    /// authorities: the authority of a node is the sum of the hub scores of
+    /// the nodes that link to it, and its hub score is the sum of the 
+    /// authorities of the nodes it links to. In weighted or multi-edge mode
+    /// each link counts with its weight. Both vectors start uniform and are
+    /// normalized to sum to one after each iteration; without any links 
+    /// they are all zero. Redistributed self-loops are not links of the 
+    /// graph here, and the pagerank is not affected.
+    /// 
+    /// Returns the authority and the hub vectors.
+    pub fn hits(&self, iterations: usize) -> (Vec<PrFloat>, Vec<PrFloat>) {
+        let num_rows = self.rows.len();
+        let keeps = self.keeps_weights();
+        let uniform = 1.0 / num_rows.max(1) as PrFloat;
+        let mut authority = vec![uniform; num_rows];
+        let mut hub = vec![uniform; num_rows];
 
-            for (k, cpr) in self.pr.iter().enumerate() {
-                sum_pr += cpr;
-                if self.num_outgoing[k] == 0 {
-                    dangling_pr += cpr;
-                }
+        let normalize = |v: &mut [PrFloat]| {
+            let sum: PrFloat = v.iter().sum();
+            if sum > 0.0 {
+                v.iter_mut().for_each(|x| *x /= sum);
             }
+        };
 
-            if num_iterations == 0 {
-                old_pr = self.pr.clone();
-            } else {
-                // Normalize so that we start with sum equal to one
-                let mut i = 0;
-                while i < self.pr.len() {
-                    old_pr[i] = self.pr[i] / sum_pr;
-                    i += 1;
-                }
+        for _ in 0..iterations {
+            for (i, row) in self.rows.iter().enumerate() {
+                authority[i] = row
+                    .iter()
+                    .enumerate()
+                    .fold(0.0, |a, (j, &from)| {
+                        let w = if keeps { self.weights[i][j] as PrFloat } else { 1.0 };
+                        a + w * hub[from]
+                    });
             }
+            normalize(&mut authority);
 
-            // After normalisation the elements of the pagerank vector sum to one
-            sum_pr = 1.0;
-
-            // An element of the A x I vector; all elements are identical
-            let one_Av = self.alpha * dangling_pr / num_rows as f64;
-
-            // An element of the 1 x I vector; all elements are identical
-            let one_Iv = (1.0 - self.alpha) * sum_pr / num_rows as f64;
+            hub.fill(0.0);
+            for (i, row) in self.rows.iter().enumerate() {
+                for (j, &from) in row.iter().enumerate() {
+                    let w = if keeps { self.weights[i][j] as PrFloat } else { 1.0 };
+                    hub[from] += w * authority[i];
+                }
+            }
+            normalize(&mut hub);
+        }
 
-            // The difference to be checked for convergence
-            diff = 0.0;
-            let mut i = 0;
-            while i < num_rows {
-                // The corresponding element of the H multiplication
-                let mut h = 0.0;
-                for ci in &self.rows[i] {
-                    let h_v = if self.num_outgoing[*ci] != 0 {
-                        1.0 / self.num_outgoing[*ci] as f64
-                    } else {
-                        0.0
-                    };
-                    if num_iterations == 0 && self.trace {
-                        println!("h[{},{}]={}", i, ci, h_v);
-                    }
+        (authority, hub)
+    }
 
-                    h += h_v * old_pr[*ci];
-                }
-                h *= self.alpha;
-                self.pr[i] = h + one_Av + one_Iv;
-                let abs = if self.pr[i] > old_pr[i] {
-                    self.pr[i] - old_pr[i]
-                } else {
-                    old_pr[i] - self.pr[i]
-                };
-                diff += abs;
+    /// Calculates the eigenvector centrality of the nodes, by index, by 
+    /// power iteration on the adjacency matrix, without damping or 
+    /// teleportation: the centrality of a node is the sum of the 
+    /// centralities of the nodes that link to it, with the weights of the 
+    /// links in weighted or multi-edge mode. The vector starts uniform, is 
+    /// normalized to sum to one after each iteration, and the iterations 
+    /// stop when the L1 norm of its change is at most tol, or after the 
+    /// given number of iterations. Redistributed self-loops are not links 
+    /// here, and the pagerank is not affected.
+    /// 
+    /// Unlike the pagerank, the iteration need not converge: on periodic 
+    /// graphs, such as bipartite ones, the vector oscillates until the 
+    /// iterations run out, and the centrality of nodes outside the strongly
+    /// connected components with cycles goes to zero; without any links 
+    /// the vector is all zero.
+    pub fn eigenvector_centrality(&self, iterations: usize, tol: PrFloat) -> Vec<PrFloat> {
+        let num_rows = self.rows.len();
+        let keeps = self.keeps_weights();
+        let mut x = vec![1.0 / num_rows.max(1) as PrFloat; num_rows];
+        let mut next = vec![0.0; num_rows];
 
-                i += 1;
+        for _ in 0..iterations {
+            for (i, row) in self.rows.iter().enumerate() {
+                next[i] = row
+                    .iter()
+                    .enumerate()
+                    .fold(0.0, |c, (j, &from)| {
+                        let w = if keeps { self.weights[i][j] as PrFloat } else { 1.0 };
+                        c + w * x[from]
+                    });
+            }
+            let sum: PrFloat = next.iter().sum();
+            if sum > 0.0 {
+                next.iter_mut().for_each(|c| *c /= sum);
             }
 
-            num_iterations += 1;
-            if self.trace {
-                print!("{}: ", num_iterations);
-                self.print_pagerank();
+            let diff: PrFloat = next.iter().zip(&x).map(|(a, b)| (a - b).abs()).sum();
+            mem::swap(&mut x, &mut next);
+            if diff <= tol {
+                break;
             }
         }
-    }
 
-    /// Returns the pagerank vector of the hyperlink matrix.
-    pub fn get_pagerank(&self) -> &Vec<f64> {
-        &self.pr
+        x
     }
 
-    /// Returns the name of the node with the given index. If the nodes are 
-    /// numeric the name is the string representation of the number. if the 
-    /// nodes are not numeric, the name is the original node name as it was 
-    /// input from read_file(&str)
-    pub fn get_node_name(&self, index: usize) -> String {
-        if self.numeric {
-            index.to_string()
-        } else {
-            self.idx_to_nodes[&index].to_string()
+    /// Returns the k nodes with the most incoming links, in descending 
+    /// order of in-degree, like top_k() does for the pagerank. Ties are 
+    /// broken by name, or by index for numeric input.
+    pub fn top_k_in_degree(&self, k: usize) -> Vec<(String, usize)> {
+        let cmp = |a: &usize, b: &usize| {
+            self.rows[*b].len().cmp(&self.rows[*a].len()).then_with(|| self.compare_nodes(*a, *b))
+        };
+
+        let mut indices: Vec<usize> = (0..self.rows.len()).collect();
+        if k < indices.len() {
+            indices.select_nth_unstable_by(k, cmp);
+            indices.truncate(k);
         }
-    }
+        indices.sort_by(cmp);
 
-    pub fn get_mapping(&self) -> &HashMap<usize, String> {
-        &self.idx_to_nodes
+        indices
+            .into_iter()
+            .map(|i| (self.get_node_name(i), self.rows[i].len()))
+            .collect()
     }
 
-    /// Returns the pagerank damping factor.
-    pub fn get_alpha(&self) -> f64 {
-        self.alpha
+    /// Sets the number of rows of the link matrix.
+    pub fn set_num_rows(&mut self, num_rows: usize) {
+        self.num_outgoing.resize(num_rows, 0);
+        self.rows.resize_with(num_rows, Vec::new);
+        if self.keeps_weights() {
+            self.weights.resize_with(num_rows, Vec::new);
+            self.out_weights.resize(num_rows, 0.0);
+        }
     }
 
-    /// Sets the pagerank damping factor.
-    pub fn set_alpha(&mut self, a: f64) {
-        self.alpha = a;
+    /// Adds an edge between the nodes named from and to, mapping the names 
+    /// to numeric IDs as read_file(&PathBuf) does for string input.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let from_idx = self.insert_mapping(from.to_string());
+        let to_idx = self.insert_mapping(to.to_string());
+        self.add_arc(from_idx, to_idx, 1.0);
     }
 
-    /// Returns the maximum number of iterations that the pagerank algorithm 
-    /// will perform.
-    pub fn get_max_iterations(&self) -> usize {
-        self.max_iterations
+    /// Adds an edge between the nodes with the (zero-based) indices from and 
+    /// to, as read_file(&PathBuf) does for numeric input.
+    pub fn add_edge_idx(&mut self, from: usize, to: usize) {
+        self.add_arc(from, to, 1.0);
     }
 
-    /// Sets the maximum number of iterations that the pagerank algorithm 
-    /// will perform.
-    pub fn set_max_iterations(&mut self, i: usize) {
-        self.max_iterations = i;
+    /// Adds an edge with the given weight between the nodes named from and 
+    /// to. The weight is only used in weighted mode.
+    /// 
+    /// Returns an error if the weight is negative or not finite.
+    pub fn add_weighted_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        weight: f64,
+    ) -> Result<(), PagerankError> {
+        Self::check_weight(weight)?;
+        let from_idx = self.insert_mapping(from.to_string());
+        let to_idx = self.insert_mapping(to.to_string());
+        self.add_arc(from_idx, to_idx, weight);
+        Ok(())
     }
 
-    /// Returns the value that is used to determine convergence of the
-    /// pagerank calculation algorithm.
-    pub fn get_convergence(&self) -> f64 {
-        self.convergence
+    /// Adds an edge with the given weight between the nodes with the 
+    /// (zero-based) indices from and to. The weight is only used in 
+    /// weighted mode.
+    /// 
+    /// Returns an error if the weight is negative or not finite.
+    pub fn add_weighted_edge_idx(
+        &mut self,
+        from: usize,
+        to: usize,
+        weight: f64,
+    ) -> Result<(), PagerankError> {
+        Self::check_weight(weight)?;
+        self.add_arc(from, to, weight);
+        Ok(())
     }
 
-    /// Sets the value that is used to determine convergence of the
-    /// pagerank calculation algorithm.
-    pub fn set_convergence(&mut self, c: f64) {
-        self.convergence = c;
-    }
+    /// Adds the graph of other to this one. For string input the nodes of 
+    /// other are matched to those of this table by name, and new names are
+    /// given new indices; for numeric input the indices are the same, and 
+    /// the labels of other are added for the indices without one. The links
+    /// of other, including its self-loops, are added with the settings of 
+    /// this table, as if they were read: repeated links are ignored unless 
+    /// multi-edges are allowed, weights are only kept in weighted mode, and
+    /// self-loops follow the self-loop policy. Since the links of an 
+    /// undirected table already contain both directions, and those of a 
+    /// table in reverse mode are already reversed, the links are added in 
+    /// the direction stored in other, and no reverse arcs are added. The 
+    /// personalization of other is not merged, and the pagerank is cleared.
+    /// 
+    /// Returns a MergeMismatch error, leaving the table unchanged, if one of
+    /// the tables has numeric input and the other does not.
+    pub fn merge(&mut self, other: &Table) -> Result<(), PagerankError> {
+        if self.numeric != other.numeric {
+            return Err(PagerankError::MergeMismatch);
+        }
 
-    /// Returns true when tracing output is enabled, false otherwise.
-    pub fn get_trace(&self) -> bool {
-        self.trace
-    }
+        // The index in this table of each node of other, in order
+        let idx: Vec<usize> = if self.numeric {
+            for (&k, label) in &other.idx_to_nodes {
+                let key = self.node_key(label).into_owned();
+                if !self.idx_to_nodes.contains_key(&k) && !self.nodes_to_idx.contains_key(&key) {
+                    self.idx_to_nodes.insert(k, label.clone());
+                    self.nodes_to_idx.insert(key, k);
+                }
+            }
+            (0..other.rows.len()).collect()
+        } else {
+            (0..other.rows.len())
+                .map(|k| self.insert_mapping(other.get_node_name(k)))
+                .collect()
+        };
+        let num_rows = idx.iter().map(|k| k + 1).max().unwrap_or(0);
+        if self.rows.len() < num_rows {
+            self.set_num_rows(num_rows);
+        }
 
-    /// Sets tracing output.
-    pub fn set_trace(&mut self, t: bool) {
-        self.trace = t;
-    }
+        for (to, row) in other.rows.iter().enumerate() {
+            for (j, &from) in row.iter().enumerate() {
+                let weight = if other.keeps_weights() { other.weights[to][j] } else { 1.0 };
+                self.merge_arc(other, idx[from], idx[to], weight);
+            }
+        }
+        for (k, w) in other.loop_weights.iter().enumerate() {
+            if let Some(w) = w {
+                self.merge_arc(other, idx[k], idx[k], *w);
+            }
+        }
 
-    /// Returns true if the graph data to be read by read_file(sting) are in 
-    /// numeric form (e.g., integer values starting from zero) or in string form.
-    pub fn get_numeric(&self) -> bool {
-        self.numeric
+        self.reset_results();
+        Ok(())
     }
 
-    /// Specifies whether the graph data to be read by read_file(sting) 
-    /// are in numeric form (e.g., integer values starting from zero) 
-    /// or in string form.
-    pub fn set_numeric(&mut self, n: bool) {
-        self.numeric = n;
+    /// Adds an arc of other with the given weight between from and to. In
+    /// unweighted multi-edge mode the weight of the arc in other is its
+    /// multiplicity, so it is added that many times.
+    fn merge_arc(&mut self, other: &Table, from: usize, to: usize, weight: f64) {
+        if other.weighted {
+            self.add_directed_arc(from, to, weight);
+        } else {
+            for _ in 0..weight as usize {
+                self.add_directed_arc(from, to, 1.0);
+            }
+        }
     }
 
-    /// Returns the delimeter used in the graph data file. The data
-    /// file is composed of lines with the following format:
-    /// <from><delim><to>
-    /// where from and to are the two graph vertices (can be either strings or
-    /// integers) and delim is the delimiter.
-    pub fn get_delim(&self) -> &str {
-        &self.delim
-    }
+    /// Keeps only the nodes for which pred, called with the index and the
+    /// name of each node, returns true, along with the links between them.
+    /// The nodes that are kept are renumbered in order, so that there are
+    /// no gaps in their indices, and keep their names; for numeric input 
+    /// without labels the names are the indices, so they change with them. 
+    /// The personalization weights of removed nodes are dropped, and the 
+    /// pagerank is cleared.
+    pub fn retain_nodes<F>(&mut self, pred: F)
+        where F: Fn(usize, &str) -> bool
+    {
+        // The new index of each node that is kept
+        let mut new_idx = vec![None; self.rows.len()];
+        let mut num_kept = 0;
+        for (k, idx) in new_idx.iter_mut().enumerate() {
+            let name = self.get_node_name(k);
+            if pred(k, &name) {
+                *idx = Some(num_kept);
+                num_kept += 1;
+            }
+        }
 
-    /// Sets the delimited to be used for reading the graph data file.
-    pub fn set_delim(&mut self, d: &str) {
-        self.delim = d.to_string();
+        let kept = self.induced(&new_idx, num_kept);
+        self.set_induced(kept);
     }
 
-    /// Outputs the parameters of the pagerank algorithm to the
-    /// given output stream. The parameters are:
-    /// - the damping factor (alpha)
-    /// - the convergence criterion (convergence)
-    /// - the maximum number of iterations (max iterations)
-    /// - whether numeric or string input is expected (numeric)
-    /// - the delimiter for separating the two vertices in each line of the
-    ///   input file (delim)
-    pub fn print_params(&self) {
-        println!("alpha = {} convergence = {} max_iterations = {} numeric = {} delimiter = '{}'", 
-            self.alpha, self.convergence, self.max_iterations, self.numeric, self.delim);
-    }
+    /// Removes the named node, along with its links, renumbering the other 
+    /// nodes as retain_nodes() does. With warm start, the pagerank of the 
+    /// other nodes is kept, renormalized to sum to one, so that the next 
+    /// calculation starts from it; otherwise the pagerank is cleared.
+    /// 
+    /// Returns false, leaving the table unchanged, if there is no node with
+    /// the given name.
+    pub fn remove_node(&mut self, name: &str) -> bool {
+        let Some(removed) = self.get_node_index(name) else {
+            return false;
+        };
 
-    /// Outputs the hyperlink table.
-    pub fn print_table(&self) {
-        let mut i = 0;
-        for cr in &self.rows {
-            print!("{}:[ ", i);
-            for cc in cr {
-                if self.numeric {
-                    print!("{} ", cc);
-                } else {
-                    print!("{} ", self.idx_to_nodes[cc]);
-                }
-            }
-            print!("]\n");
-            i += 1;
-        }
-    }
+        let kept_pr = if self.warm_start && self.pr.len() == self.rows.len() {
+            let pr: Vec<PrFloat> = self.pr
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| *k != removed)
+                .map(|(_, pr)| *pr)
+                .collect();
+            let sum: PrFloat = pr.iter().sum();
+            (sum > 0.0).then(|| pr.into_iter().map(|pr| pr / sum).collect())
+        } else {
+            None
+        };
 
-    /// Outputs the number of outgoing links for each vertex of the 
-    /// hyperlink table.
-    pub fn print_outgoing(&self) {
-        print!("[ ");
-        for cn in &self.num_outgoing {
-            print!("{} ", cn);
+        self.retain_nodes(|k, _| k != removed);
+        if let Some(pr) = kept_pr {
+            self.pr = pr;
         }
-        print!("]\n");
+        true
     }
 
-    /// Prints the pagerank vector to cout. The output format is a
-    /// series of lines:
-    /// <node> = <pagerank value> followed by a line:
-    /// s = <sum> where <sum> is the sum of the pagerank values, which
-    /// should be equal to one.
-    pub fn print_pagerank(&self) {
-        let mut sum: f64 = 0.0;
+    /// Returns the subgraph induced by the nodes with a new index in 
+    /// new_idx, with num_kept nodes, along with their names and their 
+    /// personalization weights.
+    fn induced(&self, new_idx: &[Option<usize>], num_kept: usize) -> Induced {
+        let keeps = self.keeps_weights();
 
-        print!("({}) [ ", self.pr.len());
-        for cr in &self.pr {
-            print!("{:10} ", cr);
-            sum += *cr;
-            print!("s = {} ", sum);
+        let mut rows = Vec::with_capacity(num_kept);
+        let mut weights = Vec::with_capacity(if keeps { num_kept } else { 0 });
+        for (to, row) in self.rows.iter().enumerate() {
+            if new_idx[to].is_none() {
+                continue;
+            }
+            // The new indices are in the same order, so the rows stay sorted
+            let links = row
+                .iter()
+                .enumerate()
+                .filter_map(|(j, &from)| new_idx[from].map(|i| (i, j)));
+            if keeps {
+                let (row, w): (Vec<usize>, Vec<f64>) = links
+                    .map(|(i, j)| (i, self.weights[to][j]))
+                    .unzip();
+                rows.push(row);
+                weights.push(w);
+            } else {
+                rows.push(links.map(|(i, _)| i).collect());
+            }
         }
-        
-        print!("] {}\n", sum);
+        let loop_weights = self.loop_weights
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| new_idx[*k].is_some())
+            .map(|(_, w)| *w)
+            .collect();
+
+        let names = self.idx_to_nodes
+            .iter()
+            .filter_map(|(&k, name)| new_idx.get(k).copied().flatten().map(|i| (i, name.clone())))
+            .collect();
+        let personalization = self.personalization.as_ref().map(|p| p
+            .iter()
+            .filter_map(|(&k, &w)| new_idx.get(k).copied().flatten().map(|i| (i, w)))
+            .collect());
+
+        Induced { rows, weights, loop_weights, names, personalization }
     }
 
-    /// Outputs the pageranks vector in a more verbose way than print_pagerank():
-    /// it substitutes string vertex names for numeric IDs, if available,
-    /// and also outputs the index number of each vector, starting from zero.
-    pub fn print_pagerank_v(&self) {
-        let mut i = 0;
-        let num_rows = self.pr.len();
-        let mut sum = 0.0;
+    /// Replaces the graph with the given induced subgraph.
+    fn set_induced(&mut self, g: Induced) {
+        self.rows = g.rows;
+        self.weights = g.weights;
+        self.loop_weights = g.loop_weights;
+        self.nodes_to_idx = g.names.iter().map(|(i, name)| (self.node_key(name).into_owned(), *i)).collect();
+        self.idx_to_nodes = g.names.into_iter().collect();
 
-        while i < num_rows {
+        self.personalization = None;
+        if let Some(p) = g.personalization {
+            // Without any weight left, teleportation stays uniform
+            let _ = self.set_personalization(p);
+        }
+
+        self.recount();
+    }
+
+    /// Returns an empty table with the parameters of this one, for the 
+    /// reading, the graph and the calculation, without the callbacks.
+    fn with_params(&self) -> Table {
+        Table {
+            verbosity: self.verbosity,
+            quiet: self.quiet,
+            timing: self.timing,
+            progress_interval: self.progress_interval,
+            alpha: self.alpha,
+            teleport: self.teleport,
+            convergence: self.convergence,
+            convergence_norm: self.convergence_norm,
+            max_iterations: self.max_iterations,
+            fixed_iterations: self.fixed_iterations,
+            time_budget: self.time_budget,
+            delim: self.delim.clone(),
+            extra_delims: self.extra_delims.clone(),
+            whitespace: self.whitespace,
+            trim_quotes: self.trim_quotes,
+            case_insensitive: self.case_insensitive,
+            comment_prefix: self.comment_prefix.clone(),
+            skip_header: self.skip_header,
+            strict: self.strict,
+            lenient: self.lenient,
+            count_first: self.count_first,
+            numeric: self.numeric,
+            max_index: self.max_index,
+            weighted: self.weighted,
+            allow_multi_edges: self.allow_multi_edges,
+            undirected: self.undirected,
+            reverse: self.reverse,
+            dedup_symmetric: self.dedup_symmetric,
+            self_loops: self.self_loops,
+            dangling_policy: self.dangling_policy,
+            ignore_isolated: self.ignore_isolated,
+            iteration_method: self.iteration_method,
+            output_precision: self.output_precision,
+            record_history: self.record_history,
+            warm_start: self.warm_start,
+            ..Default::default()
+        }
+    }
+
+    /// Calculates the pagerank of the neighborhood of the named seed node:
+    /// the subgraph induced by the nodes reached from the seed by following
+    /// at most hops outgoing links, including the seed itself. Only the 
+    /// links between these nodes are kept, so a node at the boundary whose 
+    /// links all lead outside the neighborhood is dangling there, and 
+    /// teleportation is spread over the neighborhood alone; the ranks are 
+    /// those of the subgraph, which add up to 1, not those of the full 
+    /// graph. The calculation uses the parameters of the table and leaves 
+    /// its graph and its pagerank unchanged. Returns the pagerank of each
+    /// node of the neighborhood keyed by node name, or None if there is no
+    /// node with the given name.
+    pub fn pagerank_local(&self, seed: &str, hops: usize) -> Option<HashMap<String, PrFloat>> {
+        let seed = self.get_node_index(seed)?;
+        let num_rows = self.rows.len();
+
+        let mut out_links = vec![Vec::new(); num_rows];
+        for (from, to) in self.edges() {
+            out_links[from].push(to);
+        }
+
+        // Breadth-first search, one hop at a time
+        let mut reached = vec![false; num_rows];
+        reached[seed] = true;
+        let mut frontier = vec![seed];
+        for _ in 0..hops {
+            let mut next = Vec::new();
+            for &k in &frontier {
+                for &to in &out_links[k] {
+                    if !reached[to] {
+                        reached[to] = true;
+                        next.push(to);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        let mut new_idx = vec![None; num_rows];
+        let mut kept = Vec::new();
+        for (k, idx) in new_idx.iter_mut().enumerate() {
+            if reached[k] {
+                *idx = Some(kept.len());
+                kept.push(k);
+            }
+        }
+
+        let mut local = self.with_params();
+        local.set_induced(self.induced(&new_idx, kept.len()));
+        local.pagerank().ok()?;
+        // Nodes are named as in this table, whatever their new indices
+        Some(kept
+            .iter()
+            .zip(local.pagerank_scores())
+            .map(|(&k, &pr)| (self.get_node_name(k), pr))
+            .collect())
+    }
+
+    /// Keeps only the links for which pred, called with the indices of the
+    /// source and the target of each link, returns true. Redistributed 
+    /// self-loops are links from a node to itself. The nodes are kept, even
+    /// without any links, and the pagerank is cleared.
+    pub fn retain_edges<F>(&mut self, pred: F)
+        where F: Fn(usize, usize) -> bool
+    {
+        let keeps = self.keeps_weights();
+        for (to, row) in self.rows.iter_mut().enumerate() {
+            if keeps {
+                let (kept, weights): (Vec<usize>, Vec<f64>) = row
+                    .iter()
+                    .zip(&self.weights[to])
+                    .filter(|(from, _)| pred(**from, to))
+                    .unzip();
+                *row = kept;
+                self.weights[to] = weights;
+            } else {
+                row.retain(|&from| pred(from, to));
+            }
+        }
+        for (k, w) in self.loop_weights.iter_mut().enumerate() {
+            if w.is_some() && !pred(k, k) {
+                *w = None;
+            }
+        }
+
+        self.recount();
+    }
+
+    /// Recalculates the number and the weight of the outgoing links of each
+    /// node from the rows and the redistributed self-loops, after links were
+    /// removed. The repeated edges are recounted if they are not weighted; 
+    /// weighted repeated edges are merged into their link, so in weighted 
+    /// multi-edge mode the repeats of removed links are still counted. Any 
+    /// pagerank calculated before no longer applies and is cleared.
+    fn recount(&mut self) {
+        let keeps = self.keeps_weights();
+        let num_rows = self.rows.len();
+
+        self.num_outgoing = vec![0; num_rows];
+        if keeps {
+            self.out_weights = vec![0.0; num_rows];
+        }
+        for (to, row) in self.rows.iter().enumerate() {
+            for (j, &from) in row.iter().enumerate() {
+                self.num_outgoing[from] += 1;
+                if keeps {
+                    self.out_weights[from] += self.weights[to][j];
+                }
+            }
+        }
+        for (k, w) in self.loop_weights.iter().enumerate() {
+            if let Some(w) = w {
+                self.num_outgoing[k] += 1;
+                if keeps {
+                    self.out_weights[k] += w;
+                }
+            }
+        }
+
+        if self.allow_multi_edges && !self.weighted {
+            self.num_repeated = self.weights
+                .iter()
+                .flatten()
+                .chain(self.loop_weights.iter().flatten())
+                .map(|w| (*w as usize).saturating_sub(1))
+                .sum();
+        }
+
+        self.reset_results();
+    }
+
+    /// Checks that an edge weight is non-negative and finite.
+    fn check_weight(weight: f64) -> Result<(), PagerankError> {
+        if weight.is_finite() && weight >= 0.0 {
+            Ok(())
+        } else {
+            Err(PagerankError::InvalidWeight(weight))
+        }
+    }
+
+    /// Parses an edge weight found in the given (zero-based) line of the input.
+    fn parse_weight(token: &str, linenum: usize) -> Result<f64, PagerankError> {
+        match token.parse::<f64>() {
+            Ok(w) if Self::check_weight(w).is_ok() => Ok(w),
+            _ => Err(PagerankError::ParseWeight {
+                line: linenum + 1,
+                token: token.to_string(),
+            }),
+        }
+    }
+
+    /// Parses a numeric vertex found in the given (zero-based) line of the input.
+    fn parse_vertex(token: &str, linenum: usize) -> Result<usize, PagerankError> {
+        token.parse().map_err(|_| PagerankError::ParseVertex {
+            line: linenum + 1,
+            token: token.to_string(),
+        })
+    }
+
+    /// Parses a numeric vertex of an edge found in the given (zero-based) 
+    /// line of the input, checking it against the maximum index, if any.
+    fn parse_index(&self, token: &str, linenum: usize) -> Result<usize, PagerankError> {
+        let index = Self::parse_vertex(token, linenum)?;
+        match self.max_index {
+            Some(max) if index > max => Err(PagerankError::IndexOutOfRange {
+                line: linenum + 1,
+                index,
+                max,
+            }),
+            _ => Ok(index),
+        }
+    }
+
+    /// Parses the numeric vertices of an edge found in the given (zero-based)
+    /// line of the input. In lenient mode an invalid vertex is logged and 
+    /// counted, and None is returned, so that the line is skipped.
+    fn parse_vertices(
+        &mut self,
+        from: &str,
+        to: &str,
+        linenum: usize,
+    ) -> Result<Option<(usize, usize)>, PagerankError> {
+        match (self.parse_index(from, linenum), self.parse_index(to, linenum)) {
+            (Ok(from), Ok(to)) => Ok(Some((from, to))),
+            (Err(e), _) | (_, Err(e)) if !self.lenient => Err(e),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("skipping {}", e);
+                self.invalid_lines += 1;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reads the graph described in filename. If count-first mode is on, the
+    /// file is read twice: once to count the vertices and reserve space for 
+    /// them, and once to build the graph. With the gzip feature enabled,
+    /// files ending in .gz are decompressed while they are read. Files ending
+    /// in .mtx (or .mtx.gz) are read as Matrix Market files, like 
+    /// read_mtx_file(&PathBuf) does. Non-blank lines without the delimiter 
+    /// are skipped, and counted by get_skipped_lines(); in strict mode they
+    /// are an error instead. For numeric input, a vertex that is not a valid
+    /// index or exceeds the maximum index is an error; in lenient mode its 
+    /// line is skipped instead, and counted by get_invalid_lines().
+    /// 
+    /// Returns an error if the file cannot be read, or for the lines that 
+    /// strict mode or the lack of lenient mode rejects.
+    pub fn read_file(&mut self, filename: &PathBuf) -> Result<(), PagerankError> {
+        self.read_files(slice::from_ref(filename))
+    }
+
+    /// Reads the union of the graphs described in files, as read_file(&PathBuf)
+    /// does for each of them. The graph read before is replaced, but the 
+    /// files add to the same graph, so a node appearing in several of them 
+    /// is the same node. In count-first mode the files are scanned to count 
+    /// the vertices of all of them before they are read.
+    /// 
+    /// Returns the first error found while reading the files.
+    pub fn read_files(&mut self, files: &[PathBuf]) -> Result<(), PagerankError> {
+        self.reset();
+
+        if self.count_first {
+            let mut names = HashSet::new();
+            let mut max_dim = 0;
+            for filename in files.iter().filter(|f| !Self::is_mtx_file(f)) {
+                self.count_rows(Self::open_file(filename)?, &mut names, &mut max_dim)?;
+            }
+            let size = if self.numeric { max_dim } else { names.len() };
+            debug!("counted {} rows", size);
+            self.reserve(size);
             if !self.numeric {
-                println!("{} = {}", self.idx_to_nodes[&i], self.pr[i]);
+                self.nodes_to_idx.reserve(size);
+                self.idx_to_nodes.reserve(size);
+            }
+        }
+
+        for filename in files {
+            let reader = Self::open_file(filename)?;
+            if Self::is_mtx_file(filename) {
+                self.read_mtx_lines(reader)?;
             } else {
-                println!("{} = {}", i, self.pr[i]);
+                self.read_lines(reader)?;
             }
-            sum += self.pr[i];
+        }
 
-            i += 1;
+        self.finish_read();
+        Ok(())
+    }
+
+    /// Reads the graph from the standard input, in the same format as 
+    /// read_file(&PathBuf).
+    pub fn read_stdin(&mut self) -> Result<(), PagerankError> {
+        self.read_from_reader(io::stdin().lock())
+    }
+
+    /// Reads the graph from the given reader, in the same format as 
+    /// read_file(&PathBuf), replacing the graph read before. It does not 
+    /// depend on the filesystem, so an in-memory edge list can be read with
+    /// e.g. read_from_reader(bytes) for bytes: &[u8].
+    pub fn read_from_reader<R: BufRead>(&mut self, reader: R) -> Result<(), PagerankError> {
+        self.reset();
+        self.read_lines(reader)?;
+        self.finish_read();
+        Ok(())
+    }
+
+    /// Reads the graph from the Matrix Market file filename, whatever its
+    /// extension. Only coordinate matrices are supported, with pattern, 
+    /// integer or real values and general or symmetric symmetry. Each entry 
+    /// (row, col) is an arc from row to col; the one-based indices of the 
+    /// file are converted to zero-based ones, and for non-numeric input the 
+    /// node names are the indices as they appear in the file. In weighted 
+    /// mode the values of the entries are used as the weights of the arcs.
+    /// A node whitelist or blacklist applies to the entries, by zero-based 
+    /// index for numeric input and by name otherwise; a skipped entry of a 
+    /// symmetric matrix counts once in get_filtered_edges().
+    /// 
+    /// Returns an error if the file cannot be read, or if its header, 
+    /// dimensions line or entries are invalid.
+    pub fn read_mtx_file(&mut self, filename: &PathBuf) -> Result<(), PagerankError> {
+        let reader = Self::open_file(filename)?;
+        self.reset();
+        self.read_mtx_lines(reader)?;
+        self.finish_read();
+        Ok(())
+    }
+
+    /// Reads the graph from the standard input, in the Matrix Market format 
+    /// of read_mtx_file(&PathBuf).
+    pub fn read_mtx_stdin(&mut self) -> Result<(), PagerankError> {
+        self.reset();
+        self.read_mtx_lines(io::stdin().lock())?;
+        self.finish_read();
+        Ok(())
+    }
+
+    /// Reads the names of nodes from the file path, with a line for each 
+    /// node: its (zero-based) index, the delimiter and its name. Numeric 
+    /// input is read without names, so the labels let get_node_name() and 
+    /// the output show names instead of indices; nodes without a label 
+    /// keep their index as their name. For string input the labels replace
+    /// the names of the nodes. Comment and header lines are ignored, like 
+    /// in graph files. Reading a graph clears the labels, so they must be 
+    /// loaded after the graph.
+    /// 
+    /// Returns an error if the file cannot be read, if an index is not a 
+    /// valid number, and in strict mode, if a line does not contain the 
+    /// delimiter.
+    pub fn load_labels(&mut self, path: &PathBuf) -> Result<(), PagerankError> {
+        let reader = Self::open_file(path)?;
+        for (linenum, line_result) in reader.lines().enumerate() {
+            let line = line_result?;
+            if self.is_ignored_line(&line, linenum) || line.trim().is_empty() {
+                continue;
+            }
+            let line = if self.whitespace { line.trim_start() } else { &line };
+            let Some((pos, end)) = self.find_delim(line) else {
+                if self.strict {
+                    return Err(PagerankError::MissingDelimiter { line: linenum + 1 });
+                }
+                continue;
+            };
+
+            let idx = Self::parse_vertex(self.unquote(line[..pos].trim()), linenum)?;
+            let label = self.unquote(line[end..].trim()).to_string();
+            if let Some(old) = self.idx_to_nodes.insert(idx, label.clone()) {
+                self.nodes_to_idx.remove(self.node_key(&old).as_ref());
+            }
+            self.nodes_to_idx.insert(self.node_key(&label).into_owned(), idx);
+        }
+
+        Ok(())
+    }
+
+    /// Reads the personalization vector from the file path, with a line for
+    /// each seed node: its name (or index, for numeric input), the 
+    /// delimiter and its weight. The weights are normalized as by 
+    /// set_personalization(); nodes that are not listed get zero, and the 
+    /// weights of a node listed more than once are added. Comment and 
+    /// header lines are ignored, like in graph files. Reading a graph does
+    /// not clear the personalization, but the nodes are looked up in the 
+    /// graph, so it must be loaded after the graph.
+    /// 
+    /// Returns an error if the file cannot be read, if a node is not in the
+    /// graph, if a weight is not a valid non-negative number, if the 
+    /// weights sum to zero, and in strict mode, if a line does not contain 
+    /// the delimiter.
+    pub fn load_personalization(&mut self, path: &PathBuf) -> Result<(), PagerankError> {
+        let reader = Self::open_file(path)?;
+        let mut weights = HashMap::new();
+        for (linenum, line_result) in reader.lines().enumerate() {
+            let line = line_result?;
+            if self.is_ignored_line(&line, linenum) || line.trim().is_empty() {
+                continue;
+            }
+            let line = if self.whitespace { line.trim_start() } else { &line };
+            let Some((pos, end)) = self.find_delim(line) else {
+                if self.strict {
+                    return Err(PagerankError::MissingDelimiter { line: linenum + 1 });
+                }
+                continue;
+            };
+
+            let name = self.unquote(line[..pos].trim());
+            let Some(idx) = self.get_node_index(name) else {
+                return Err(PagerankError::UnknownNode {
+                    line: linenum + 1,
+                    name: name.to_string(),
+                });
+            };
+            let weight = Self::parse_weight(line[end..].trim(), linenum)?;
+            *weights.entry(idx).or_insert(0.0) += weight;
+        }
+
+        self.set_personalization(weights)
+    }
+
+    /// Reads a whitelist of nodes from the file path, with a node per line:
+    /// its name, or for numeric input, its index. Only the edges of the 
+    /// graph data file between two listed nodes are read from then on, so
+    /// that the other nodes are never added; the number of edges skipped is
+    /// returned by get_filtered_edges(). The list replaces any whitelist or
+    /// blacklist loaded before, and is kept while graphs are read, until 
+    /// clear_node_filter() is called. Comment and header lines are ignored,
+    /// like in graph files. The numeric mode must be set before loading it.
+    /// 
+    /// Returns an error if the file cannot be read, or for numeric input, if
+    /// a node is not a valid index.
+    pub fn load_node_whitelist(&mut self, path: &PathBuf) -> Result<(), PagerankError> {
+        self.node_filter = Some(self.read_node_filter(path, true)?);
+        Ok(())
+    }
+
+    /// Reads a blacklist of nodes from the file path, in the format of 
+    /// load_node_whitelist(&PathBuf). Only the edges of the graph data file
+    /// without any listed node are read from then on.
+    /// 
+    /// Returns an error as load_node_whitelist(&PathBuf) does.
+    pub fn load_node_blacklist(&mut self, path: &PathBuf) -> Result<(), PagerankError> {
+        self.node_filter = Some(self.read_node_filter(path, false)?);
+        Ok(())
+    }
+
+    /// Removes the whitelist or blacklist of nodes, so that all the edges 
+    /// are read.
+    pub fn clear_node_filter(&mut self) {
+        self.node_filter = None;
+    }
+
+    /// Reads the nodes of a whitelist or blacklist from the file path.
+    fn read_node_filter(&self, path: &PathBuf, whitelist: bool) -> Result<NodeFilter, PagerankError> {
+        let reader = Self::open_file(path)?;
+        let mut filter = NodeFilter { names: HashSet::new(), indices: HashSet::new(), whitelist };
+        for (linenum, line_result) in reader.lines().enumerate() {
+            let line = line_result?;
+            if self.is_ignored_line(&line, linenum) || line.trim().is_empty() {
+                continue;
+            }
+
+            let node = self.unquote(line.trim());
+            if self.numeric {
+                filter.indices.insert(Self::parse_vertex(node, linenum)?);
+            } else {
+                filter.names.insert(self.node_key(node).into_owned());
+            }
+        }
+
+        Ok(filter)
+    }
+
+    /// Opens filename for reading, decompressing it if it ends in .gz and 
+    /// the gzip feature is enabled.
+    fn open_file(filename: &PathBuf) -> Result<Box<dyn BufRead>, PagerankError> {
+        let file = File::open(filename)?;
+
+        #[cfg(feature = "gzip")]
+        if filename.extension().is_some_and(|ext| ext == "gz") {
+            return Ok(Box::new(BufReader::new(GzDecoder::new(file))));
+        }
+
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    /// Returns true if filename ends in .mtx, or, with the gzip feature 
+    /// enabled, in .mtx.gz.
+    fn is_mtx_file(filename: &Path) -> bool {
+        #[cfg(feature = "gzip")]
+        if filename.extension().is_some_and(|ext| ext == "gz") {
+            if let Some(stem) = filename.file_stem() {
+                return Self::is_mtx_file(Path::new(stem));
+            }
+        }
+
+        filename.extension().is_some_and(|ext| ext == "mtx")
+    }
+
+    /// Returns true if the given (zero-based) line of the graph data is 
+    /// part of the header or a comment, and so is not read.
+    fn is_ignored_line(&self, line: &str, linenum: usize) -> bool {
+        linenum < self.skip_header
+            || (!self.comment_prefix.is_empty() && line.trim_start().starts_with(&self.comment_prefix))
+    }
+
+    /// Splits a line of the graph data into the source and target vertices 
+    /// of the edge and, in weighted mode, its weight, if present. In 
+    /// whitespace mode these are the first fields of the line, and any 
+    /// further fields are ignored.
+    /// 
+    /// Returns None if the line does not contain the delimiter.
+    fn split_line<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str, Option<&'a str>)> {
+        let line = if self.whitespace { line.trim_start() } else { line };
+        let (pos, end) = self.find_delim(line)?;
+
+        let from = self.unquote(line[0..pos].trim());
+        let to = line[end..].trim();
+        if self.weighted || self.whitespace {
+            if let Some((wpos, wend)) = self.find_delim(to) {
+                let rest = to[wend..].trim();
+                let weight = if self.whitespace {
+                    self.find_delim(rest).map_or(rest, |(pos, _)| &rest[..pos])
+                } else {
+                    rest
+                };
+                let weight = self.weighted.then(|| self.unquote(weight));
+                return Some((from, self.unquote(to[..wpos].trim()), weight));
+            }
+        }
+        Some((from, self.unquote(to), None))
+    }
+
+    /// Returns the start and the end of the first delimiter in line; in 
+    /// whitespace mode the delimiter is the first run of whitespace. With 
+    /// several delimiters, the first one found is used, and the longest if
+    /// more than one starts there. If quotes are trimmed, delimiters 
+    /// between double quotes are part of a field and are not considered.
+    fn find_delim(&self, line: &str) -> Option<(usize, usize)> {
+        if !self.trim_quotes && !self.whitespace && self.extra_delims.is_empty() {
+            return line.find(self.delim.as_str()).map(|pos| (pos, pos + self.delim.len()));
+        }
+
+        let mut quoted = false;
+        for (i, c) in line.char_indices() {
+            if c == '"' && self.trim_quotes {
+                quoted = !quoted;
+            } else if quoted {
+                continue;
+            } else if self.whitespace && c.is_whitespace() {
+                let len = line[i..].find(|c: char| !c.is_whitespace()).unwrap_or(line.len() - i);
+                return Some((i, i + len));
+            } else if !self.whitespace {
+                if let Some(len) = self.delim_len_at(&line[i..]) {
+                    return Some((i, i + len));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the length of the longest delimiter that rest starts with, 
+    /// if any.
+    fn delim_len_at(&self, rest: &str) -> Option<usize> {
+        iter::once(&self.delim)
+            .chain(&self.extra_delims)
+            .filter(|d| !d.is_empty() && rest.starts_with(d.as_str()))
+            .map(String::len)
+            .max()
+    }
+
+    /// Removes the double quotes around a field of a line, if quotes are 
+    /// trimmed.
+    fn unquote<'a>(&self, field: &'a str) -> &'a str {
+        if self.trim_quotes && field.len() >= 2 && field.starts_with('"') && field.ends_with('"') {
+            &field[1..field.len() - 1]
+        } else {
+            field
+        }
+    }
+
+    /// Scans the lines of the given reader, without building the graph, and 
+    /// counts the rows the graph needs: the distinct vertices are added to 
+    /// names, or for numeric input, max_dim is raised to the highest index 
+    /// plus one.
+    fn count_rows<R: BufRead>(
+        &self,
+        reader: R,
+        names: &mut HashSet<String>,
+        max_dim: &mut usize,
+    ) -> Result<(), PagerankError> {
+        for (linenum, line_result) in reader.lines().enumerate() {
+            let line = line_result?;
+            if self.is_ignored_line(&line, linenum) {
+                continue;
+            }
+            if let Some((from, to, _)) = self.split_line(&line) {
+                if self.numeric {
+                    match (self.parse_index(from, linenum), self.parse_index(to, linenum)) {
+                        (Ok(from), Ok(to)) => *max_dim = (*max_dim).max(from.max(to) + 1),
+                        (Err(e), _) | (_, Err(e)) if !self.lenient => return Err(e),
+                        _ => {}
+                    }
+                } else {
+                    if !names.contains(from) {
+                        names.insert(from.to_string());
+                    }
+                    if !names.contains(to) {
+                        names.insert(to.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the edges in the lines of the given reader, adding them to the 
+    /// graph.
+    fn read_lines<R: BufRead>(&mut self, reader: R) -> Result<(), PagerankError> {
+        let mut linenum = 0;
+        for line_result in reader.lines() {
+            let line = line_result?;
+            let ignored = self.is_ignored_line(&line, linenum);
+            let edge = if ignored { None } else { self.split_line(&line) };
+            
+            if !ignored && edge.is_none() && !line.trim().is_empty() {
+                if self.strict {
+                    return Err(PagerankError::MissingDelimiter { line: linenum + 1 });
+                }
+                self.skipped_lines += 1;
+            }
+
+            if let Some((from, to, weight)) = edge {
+                let weight = match weight {
+                    Some(token) => Self::parse_weight(token, linenum)?,
+                    None => 1.0,
+                };
+
+                let vertices = if !self.numeric {
+                    if self.is_filtered_name_edge(from, to) {
+                        self.filtered_edges += 1;
+                        None
+                    } else {
+                        Some((self.insert_mapping(from.to_string()), self.insert_mapping(to.to_string())))
+                    }
+                } else {
+                    match self.parse_vertices(from, to, linenum)? {
+                        Some((from_idx, to_idx)) if self.is_filtered_index_edge(from_idx, to_idx) => {
+                            self.filtered_edges += 1;
+                            None
+                        }
+                        v => v,
+                    }
+                };
+                if let Some((from_idx, to_idx)) = vertices {
+                    if self.is_symmetric_duplicate(from_idx, to_idx) {
+                        trace!("skipped {} => {}, the reverse of an edge read before", from_idx, to_idx);
+                        self.symmetric_duplicates += 1;
+                    } else {
+                        self.add_arc(from_idx, to_idx, weight);
+                    }
+                }
+            }
+
+            linenum += 1;
+            if self.progress_interval != 0 && linenum % self.progress_interval == 0 {
+                self.print_progress(linenum);
+            }
+        }
+
+        self.print_progress(linenum);
+
+        Ok(())
+    }
+
+    /// Returns true if the edge between the named nodes is skipped by the 
+    /// node filter, for string input.
+    fn is_filtered_name_edge(&self, from: &str, to: &str) -> bool {
+        self.node_filter.as_ref().is_some_and(|f| {
+            let listed = |name| f.names.contains(self.node_key(name).as_ref());
+            !f.keeps(listed(from), listed(to))
+        })
+    }
+
+    /// Returns true if the edge between the given nodes is skipped by the 
+    /// node filter, for numeric input.
+    fn is_filtered_index_edge(&self, from: usize, to: usize) -> bool {
+        self.node_filter
+            .as_ref()
+            .is_some_and(|f| !f.keeps(f.indices.contains(&from), f.indices.contains(&to)))
+    }
+
+    /// Returns true if the edge between from and to is skipped because its 
+    /// reverse was read before, when symmetric duplicates are removed; 
+    /// otherwise the edge is remembered so that its reverse is skipped.
+    fn is_symmetric_duplicate(&mut self, from: usize, to: usize) -> bool {
+        if !self.dedup_symmetric || from == to {
+            return false;
+        }
+        if self.read_edges.contains(&(to, from)) {
+            return true;
+        }
+        self.read_edges.insert((from, to));
+        false
+    }
+
+    /// Reports the number of lines read so far to the progress callback, if
+    /// one is set, or else to stdout, unless quiet or silent.
+    fn print_progress(&mut self, linenum: usize) {
+        let num_rows = self.rows.len();
+        if let Some(callback) = self.progress_callback.as_mut() {
+            callback(linenum, num_rows);
+        } else if !self.quiet && self.verbosity > Verbosity::Silent {
+            println!("read {} lines, {} vertices", linenum, num_rows);
+        }
+    }
+
+    /// Prepares the graph that was read for the pagerank calculation.
+    fn finish_read(&mut self) {
+        if !self.numeric {
+            self.reserve(self.idx_to_nodes.len());
+        }
+        self.read_edges = HashSet::new();
+        self.read_time = self.read_start.take().map(|start| start.elapsed());
+    }
+
+    /// Reads the entries in the lines of the given reader, in the Matrix 
+    /// Market format of read_mtx_file(&PathBuf), adding them to the graph.
+    fn read_mtx_lines<R: BufRead>(&mut self, reader: R) -> Result<(), PagerankError> {
+        let invalid = |linenum: usize, reason: &str| PagerankError::InvalidMatrixMarket {
+            line: linenum + 1,
+            reason: reason.to_string(),
+        };
+
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(line) => line?,
+            None => return Err(invalid(0, "missing header")),
+        };
+        let header: Vec<String> = header.split_whitespace().map(str::to_lowercase).collect();
+        if header.len() != 5 || header[0] != "%%matrixmarket" || header[1] != "matrix" {
+            return Err(invalid(0, "invalid header"));
+        }
+        if header[2] != "coordinate" {
+            return Err(invalid(0, "only coordinate matrices are supported"));
+        }
+        let pattern = match header[3].as_str() {
+            "pattern" => true,
+            "integer" | "real" => false,
+            _ => return Err(invalid(0, "only pattern, integer and real matrices are supported")),
+        };
+        let symmetric = match header[4].as_str() {
+            "general" => false,
+            "symmetric" => true,
+            _ => return Err(invalid(0, "only general and symmetric matrices are supported")),
+        };
+
+        let mut linenum = 1;
+        let mut dims: Option<(usize, usize)> = None;
+        for line_result in lines {
+            let line = line_result?;
+            let line = line.trim();
+
+            if !line.is_empty() && !line.starts_with('%') {
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+                match dims {
+                    None => {
+                        if tokens.len() != 3 {
+                            return Err(invalid(linenum, "invalid dimensions line"));
+                        }
+                        let num_rows = Self::parse_vertex(tokens[0], linenum)?;
+                        let num_cols = Self::parse_vertex(tokens[1], linenum)?;
+                        let size = num_rows.max(num_cols);
+                        match self.max_index {
+                            Some(max) if size > max + 1 => {
+                                return Err(PagerankError::IndexOutOfRange {
+                                    line: linenum + 1,
+                                    index: size - 1,
+                                    max,
+                                });
+                            }
+                            _ => {}
+                        }
+                        if !self.numeric {
+                            for i in 1..=size {
+                                self.insert_mapping(i.to_string());
+                            }
+                        }
+                        if size > self.rows.len() {
+                            self.set_num_rows(size);
+                        }
+                        dims = Some((num_rows, num_cols));
+                    }
+                    Some((num_rows, num_cols)) => {
+                        if tokens.len() != if pattern { 2 } else { 3 } {
+                            return Err(invalid(linenum, "invalid entry"));
+                        }
+                        let row = Self::parse_vertex(tokens[0], linenum)?;
+                        let col = Self::parse_vertex(tokens[1], linenum)?;
+                        if row == 0 || row > num_rows || col == 0 || col > num_cols {
+                            return Err(invalid(linenum, "entry outside the matrix dimensions"));
+                        }
+                        let weight = if self.weighted && !pattern {
+                            Self::parse_weight(tokens[2], linenum)?
+                        } else {
+                            1.0
+                        };
+
+                        let filtered = if self.numeric {
+                            self.is_filtered_index_edge(row - 1, col - 1)
+                        } else {
+                            self.is_filtered_name_edge(&row.to_string(), &col.to_string())
+                        };
+                        if filtered {
+                            self.filtered_edges += 1;
+                        } else {
+                            self.add_arc(row - 1, col - 1, weight);
+                            if symmetric && row != col && !self.undirected {
+                                self.add_arc(col - 1, row - 1, weight);
+                            }
+                        }
+                    }
+                }
+            }
+
+            linenum += 1;
+            if self.progress_interval != 0 && linenum % self.progress_interval == 0 {
+                self.print_progress(linenum);
+            }
+        }
+
+        if dims.is_none() {
+            return Err(invalid(linenum, "missing dimensions line"));
         }
 
-        print!("s = {} \n", sum);
+        self.print_progress(linenum);
+
+        Ok(())
+    }
+
+    /// Returns true if the given node has no outgoing links, or, in weighted 
+    /// mode, if all its outgoing links have zero weight.
+    fn is_dangling(&self, k: usize) -> bool {
+        self.num_outgoing[k] == 0 || (self.keeps_weights() && self.out_weights[k] == 0.0)
     }
-}
\ No newline at end of file
+
+    /// Builds the compressed representation of the rows, with the elements 
+    /// of the H matrix: in weighted or multi-edge mode the weight of each 
+    /// link divided by the total weight of the links of its source, otherwise
+    /// one divided by the number of outgoing links of its source.
+    fn build_csr(&self) -> Csr {
+        if self.keeps_weights() {
+            Csr::from_rows(&self.rows, |i, j, ci| {
+                if self.out_weights[ci] != 0.0 {
+                    (self.weights[i][j] / self.out_weights[ci]) as PrFloat
+                } else {
+                    0.0
+                }
+            })
+        } else {
+            Csr::from_rows(&self.rows, |_, _, ci| {
+                if self.num_outgoing[ci] != 0 {
+                    1.0 / self.num_outgoing[ci] as PrFloat
+                } else {
+                    0.0
+                }
+            })
+        }
+    }
+
+    /// Returns the compressed representation of the rows with the elements
+    /// of the H matrix.
+    #[cfg(feature = "sprs")]
+    pub(crate) fn h_matrix(&self) -> Csr {
+        self.build_csr()
+    }
+
+    /// Returns the element of the H multiplication for the given row.
+    fn h_row(csr: &Csr, old_pr: &[PrFloat], i: usize) -> PrFloat {
+        csr.row(i)
+            .iter()
+            .zip(csr.values(i))
+            .map(|(ci, h_v)| h_v * old_pr[*ci])
+            .sum()
+    }
+
+    /// Logs the non-zero elements of the H matrix.
+    fn trace_h(&self, csr: &Csr) {
+        for i in 0..self.rows.len() {
+            for (ci, h_v) in csr.row(i).iter().zip(csr.values(i)) {
+                trace!("h[{},{}]={}", i, ci, h_v);
+            }
+        }
+    }
+
+    /// Calculates the next pagerank vector from old_pr, adding the terms of
+    /// the A x I and 1 x I vectors to every element of the H multiplication.
+    /// 
+    /// Returns the difference between the new and the old vector, in the 
+    /// convergence norm.
+    #[cfg(not(feature = "rayon"))]
+    fn update_pagerank(
+        &mut self,
+        csr: &Csr,
+        old_pr: &[PrFloat],
+        terms: &Terms,
+    ) -> PrFloat {
+        let norm = self.convergence_norm;
+        let mut diff = 0.0;
+        for (i, pr) in self.pr.iter_mut().enumerate() {
+            let h = Self::h_row(csr, old_pr, i);
+            *pr = terms.element(h, i);
+            diff = norm.combine(diff, norm.term(*pr - old_pr[i]));
+        }
+        norm.finish(diff)
+    }
+
+    /// Calculates the next pagerank vector from old_pr, adding the terms of
+    /// the A x I and 1 x I vectors to every element of the H multiplication.
+    /// The rows are calculated in parallel.
+    /// 
+    /// Returns the difference between the new and the old vector, in the 
+    /// convergence norm.
+    #[cfg(feature = "rayon")]
+    fn update_pagerank(
+        &mut self,
+        csr: &Csr,
+        old_pr: &[PrFloat],
+        terms: &Terms,
+    ) -> PrFloat {
+        let norm = self.convergence_norm;
+        let diff = self.pr
+            .par_iter_mut()
+            .enumerate()
+            .map(|(i, pr)| {
+                let h = Self::h_row(csr, old_pr, i);
+                *pr = terms.element(h, i);
+                norm.term(*pr - old_pr[i])
+            })
+            .reduce(|| 0.0, |a, b| norm.combine(a, b));
+        norm.finish(diff)
+    }
+
+    /// Calculates the next pagerank vector in place, like update_pagerank(),
+    /// but using the new values of the rows already calculated in this 
+    /// iteration instead of their values in old_pr. Since those values no
+    /// longer sum to one, the new vector is then renormalized.
+    /// 
+    /// Returns the difference between the new and the old vector, in the 
+    /// convergence norm.
+    fn update_pagerank_in_place(
+        &mut self,
+        csr: &Csr,
+        old_pr: &[PrFloat],
+        terms: &Terms,
+    ) -> PrFloat {
+        let norm = self.convergence_norm;
+        let mut diff = 0.0;
+        self.pr.copy_from_slice(old_pr);
+        for (i, &old) in old_pr.iter().enumerate() {
+            let h = Self::h_row(csr, &self.pr, i);
+            let pr = terms.element(h, i);
+            diff = norm.combine(diff, norm.term(pr - old));
+            self.pr[i] = pr;
+        }
+        let sum: PrFloat = self.pr.iter().sum();
+        if sum > 0.0 {
+            for pr in self.pr.iter_mut() {
+                *pr /= sum;
+            }
+        }
+        norm.finish(diff)
+    }
+
+    /// Calculates the next pagerank vector from old_pr, like 
+    /// update_pagerank(), but by adding the pagerank of each node to the 
+    /// rows of its outgoing links, given by out_csr, the transpose of the 
+    /// rows of the H matrix.
+    /// 
+    /// Returns the difference between the new and the old vector, in the 
+    /// convergence norm.
+    fn update_pagerank_push(
+        &mut self,
+        out_csr: &Csr,
+        old_pr: &[PrFloat],
+        terms: &Terms,
+    ) -> PrFloat {
+        let norm = self.convergence_norm;
+        let mut diff = 0.0;
+        self.pr.fill(0.0);
+        for (ci, &cpr) in old_pr.iter().enumerate() {
+            for (i, h_v) in out_csr.row(ci).iter().zip(out_csr.values(ci)) {
+                self.pr[*i] += h_v * cpr;
+            }
+        }
+        for (i, pr) in self.pr.iter_mut().enumerate() {
+            *pr = terms.element(*pr, i);
+            diff = norm.combine(diff, norm.term(*pr - old_pr[i]));
+        }
+        norm.finish(diff)
+    }
+
+    /// Calculates the pagerank of the hyperlink matrix.
+    /// 
+    /// Returns the number of iterations performed and whether the calculation
+    /// converged before reaching the maximum number of iterations, or an 
+    /// EmptyGraph error if the graph has no nodes, e.g. because nothing was
+    /// read. The results of a previous calculation are then cleared.
+    pub fn pagerank(&mut self) -> Result<PagerankResult, PagerankError> {
+        let mut diff: PrFloat = 1.0;
+        let mut sum_pr: PrFloat;  // sum of current pagerank vector elements
+        let mut dangling_pr: PrFloat;  // sum of current pagerank vector elements for dangling nodes
+        let mut num_iterations = 0;
+        let mut old_pr: Vec<PrFloat>;
+
+        let num_rows = self.rows.len();
+        let start = self.timing.then(Instant::now);
+        let deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        let mut timed_out = false;
+
+        if num_rows == 0 {
+            self.reset_results();
+            return Err(PagerankError::EmptyGraph);
+        }
+
+        self.history.clear();
+
+        // The compressed rows are only kept during the calculation, so that
+        // the graph is not stored twice
+        let csr = self.build_csr();
+        // The outgoing links of each node, for the push method only
+        let out_csr = (self.iteration_method == IterationMethod::Push).then(|| csr.transpose());
+
+        let personalization = self.personalization.as_ref().map(|weights| {
+            let mut p = vec![0.0; num_rows];
+            for (&k, &w) in weights {
+                if k < num_rows {
+                    p[k] = w as PrFloat;
+                }
+            }
+            p
+        });
+
+        // The nodes with links, among which the uniform teleport and the 
+        // pagerank of dangling nodes are shared if isolated nodes are ignored
+        let live: Option<Vec<bool>> = if self.ignore_isolated {
+            Some((0..num_rows)
+                .map(|k| self.num_outgoing[k] > 0 || !self.rows[k].is_empty())
+                .collect())
+        } else {
+            None
+        };
+        let live = live.filter(|l| l.contains(&true));
+        let num_shared = match &live {
+            Some(l) => l.iter().filter(|&&k| k).count(),
+            None => num_rows,
+        };
+
+        // The teleport probability, and the mass it adds beyond 1 - alpha
+        let beta = self.get_teleport();
+        let excess = match self.teleport {
+            Some(b) => self.alpha + b - 1.0,
+            None => 0.0,
+        };
+
+        // Start from the uniform distribution, or continue from a checkpoint,
+        // or with warm start, from the pagerank of the last calculation
+        let warm = self.warm_start 
+            && self.pr.len() == num_rows 
+            && self.pr.iter().sum::<PrFloat>() > 0.0;
+        match self.resume.take() {
+            Some(c) => {
+                self.pr = c.pr;
+                num_iterations = c.iterations;
+                diff = c.diff;
+            }
+            None if warm => {}
+            None => {
+                self.pr.clear();
+                self.pr.resize(num_rows, 1.0 / num_rows as PrFloat);
+            }
+        }
+        old_pr = vec![0.0; num_rows];
+
+        if log_enabled!(Level::Trace) {
+            trace!("{}", self.format_pagerank());
+        }
+
+        // A fixed number of iterations replaces both the convergence check 
+        // and the maximum number of iterations
+        let (check, limit) = match self.fixed_iterations {
+            Some(n) => (false, n),
+            None => (true, self.max_iterations),
+        };
+        let first_iteration = num_iterations;
+
+        while (!check || diff > self.convergence) && num_iterations < limit {
+            // The vector of the last iteration is kept when time runs out
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                timed_out = true;
+                break;
+            }
+
+            sum_pr = 0.0;
+            dangling_pr = 0.0;
+
+            for (k, cpr) in self.pr.iter().enumerate() {
+                sum_pr += cpr;
+                if self.is_dangling(k) {
+                    dangling_pr += cpr;
+                } else if !self.loop_weights.is_empty() {
+                    dangling_pr += cpr * self.loop_share(k);
+                }
+            }
+
+            // Normalize so that we start with sum equal to one, and keep the
+            // normalized vector as the old one; the new one is written over 
+            // the buffer of the previous iteration
+            for pr in self.pr.iter_mut() {
+                *pr /= sum_pr;
+            }
+            mem::swap(&mut self.pr, &mut old_pr);
+
+            // After normalisation the elements of the pagerank vector sum to one
+            sum_pr = 1.0;
+
+            // An element of the A x I vector; all elements are identical.
+            // The pagerank of dangling nodes that teleport is added to the 
+            // 1 x I vector instead, and that of ignored dangling nodes is lost
+            let teleport = self.dangling_policy == DanglingPolicy::Teleport 
+                && personalization.is_some();
+            let one_av = if self.dangling_policy == DanglingPolicy::Ignore || teleport {
+                0.0
+            } else {
+                self.alpha * dangling_pr / num_shared as PrFloat
+            };
+
+            // An element of the 1 x I vector; all elements are identical,
+            // unless they are weighted by the personalization vector
+            let one_iv = if teleport {
+                beta * sum_pr + self.alpha * dangling_pr
+            } else if personalization.is_some() {
+                beta * sum_pr
+            } else {
+                beta * sum_pr / num_shared as PrFloat
+            };
+
+            // The factor that renormalizes the pagerank vector when the 
+            // pagerank of dangling nodes is lost, or when the damping factor
+            // and the teleport probability do not sum to one
+            let lost = if self.dangling_policy == DanglingPolicy::Ignore {
+                self.alpha * dangling_pr
+            } else {
+                0.0
+            };
+            let scale = 1.0 / (1.0 - lost + excess);
+
+            let terms = Terms {
+                alpha: self.alpha,
+                one_av,
+                one_iv,
+                personalization: personalization.as_deref(),
+                live: live.as_deref(),
+                scale,
+            };
+
+            if num_iterations == 0 && log_enabled!(Level::Trace) {
+                self.trace_h(&csr);
+            }
+
+            // The difference to be checked for convergence
+            diff = match self.iteration_method {
+                IterationMethod::Jacobi => self.update_pagerank(&csr, &old_pr, &terms),
+                IterationMethod::GaussSeidel => self.update_pagerank_in_place(&csr, &old_pr, &terms),
+                IterationMethod::Push => self.update_pagerank_push(
+                    out_csr.as_ref().expect("outgoing links built for push"), &old_pr, &terms),
+            };
+
+            num_iterations += 1;
+            debug!("{}: diff = {}", num_iterations, diff);
+            if self.record_history {
+                self.history.push(diff);
+            }
+            if log_enabled!(Level::Trace) {
+                trace!("{}: {}", num_iterations, self.format_pagerank());
+                if let Some(k) = self.max_change_node(&old_pr) {
+                    trace!("{}: slowest-converging node = {}", num_iterations, self.get_node_name(k));
+                }
+            }
+
+            if let Some(callback) = self.iteration_callback.as_mut() {
+                callback(num_iterations, diff, &self.pr);
+            }
+        }
+
+        self.slowest_node = if num_iterations > first_iteration {
+            self.max_change_node(&old_pr)
+        } else {
+            None
+        };
+
+        let result = PagerankResult {
+            iterations: num_iterations,
+            converged: diff <= self.convergence && !timed_out,
+            final_diff: diff,
+            timed_out,
+        };
+        self.last_result = Some(result);
+        self.pagerank_time = start.map(|start| start.elapsed());
+
+        if let Err(e) = self.check_pagerank() {
+            warn!("{}", e);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the index of the node whose pagerank differs most from its 
+    /// value in old_pr, or None for an empty graph.
+    fn max_change_node(&self, old_pr: &[PrFloat]) -> Option<usize> {
+        self.pr
+            .iter()
+            .zip(old_pr)
+            .map(|(pr, old)| (pr - old).abs())
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(k, _)| k)
+    }
+
+    /// Returns the index of the node whose pagerank changed most in the 
+    /// last iteration of the last calculation, the one that converges 
+    /// slowest, or None if no iteration was performed. With tracing, it is 
+    /// also logged after each iteration.
+    pub fn get_slowest_node(&self) -> Option<usize> {
+        self.slowest_node
+    }
+
+    /// Checks that the pagerank vector is a probability distribution: all 
+    /// its values are finite and non-negative, and their sum is one, within 
+    /// a small tolerance.
+    /// 
+    /// Returns a Diverged error with the sum of the values if it is not.
+    pub fn check_pagerank(&self) -> Result<(), PagerankError> {
+        let sum: PrFloat = self.pr.iter().sum();
+        let valid = self.pr.iter().all(|p| p.is_finite() && *p >= 0.0);
+
+        if valid && (self.pr.is_empty() || (sum - 1.0).abs() <= SUM_TOLERANCE) {
+            Ok(())
+        } else {
+            Err(PagerankError::Diverged(sum))
+        }
+    }
+
+    /// Outputs the state of the last calculation to the given output stream,
+    /// as a checkpoint that resume_from() can load to continue it: the 
+    /// number of iterations performed, the difference of the last one and 
+    /// the pagerank vector. The graph and the parameters are not included.
+    pub fn save_checkpoint<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let (iterations, diff) = match self.last_result {
+            Some(r) => (r.iterations, r.final_diff),
+            None => (0, 1.0),
+        };
+        Self::write_checkpoint(w, iterations, diff, &self.pr)
+    }
+
+    /// Outputs a checkpoint in the format of save_checkpoint(), for the 
+    /// state after the given number of iterations, with the difference of 
+    /// the last one and the pagerank vector it produced. It can be called 
+    /// from an iteration callback, with its arguments, to save the state 
+    /// of a calculation in progress.
+    pub fn write_checkpoint<W: Write>(
+        w: &mut W,
+        iterations: usize,
+        diff: PrFloat,
+        pr: &[PrFloat],
+    ) -> io::Result<()> {
+        checkpoint::write(w, iterations, diff, pr)
+    }
+
+    /// Loads a checkpoint written by save_checkpoint() or 
+    /// write_checkpoint(), so that the next call of pagerank() continues 
+    /// the calculation from it instead of starting from the uniform 
+    /// distribution. The iterations of the checkpoint count towards the 
+    /// maximum number of iterations, and the result of the calculation 
+    /// includes them. The graph the checkpoint was saved for must already 
+    /// be loaded; the checkpoint is dropped when the results are reset.
+    /// 
+    /// Returns an InvalidCheckpoint error if the checkpoint is invalid or 
+    /// its number of nodes differs from the graph.
+    pub fn resume_from<R: Read>(&mut self, r: &mut R) -> Result<(), PagerankError> {
+        let c = checkpoint::read(BufReader::new(r), self.rows.len())?;
+        self.reset_results();
+        self.resume = Some(c);
+        Ok(())
+    }
+
+    /// Returns the difference between successive pagerank vectors after each
+    /// iteration of the last calculation, if recording is enabled; otherwise
+    /// the history is empty.
+    pub fn get_convergence_history(&self) -> &[PrFloat] {
+        &self.history
+    }
+
+    /// Estimates the number of iterations that pagerank() needs to reach the
+    /// convergence criterion, counting from the start of the calculation. 
+    /// The difference between successive vectors shrinks roughly by a 
+    /// constant ratio, at most alpha, in each iteration. If the history of 
+    /// a calculation with at least two iterations was recorded, e.g. of a 
+    /// few fixed iterations, the ratio of its last two differences is used 
+    /// to extrapolate from the last one; otherwise the estimate uses alpha,
+    /// starting from a difference of one. The estimate is rough, and at 
+    /// most the maximum number of iterations.
+    pub fn estimated_iterations(&self) -> usize {
+        let (done, diff, ratio) = match self.history.as_slice() {
+            [.., prev, last] if *prev > 0.0 => (self.history.len(), *last, last / prev),
+            _ => (0, 1.0, self.alpha),
+        };
+        if diff <= self.convergence {
+            return done.min(self.max_iterations);
+        }
+        if ratio.is_nan() || ratio >= 1.0 {
+            return self.max_iterations;
+        }
+
+        // diff * ratio^n <= convergence
+        let remaining = ((self.convergence / diff).ln() / ratio.ln()).ceil();
+        let remaining = if remaining.is_finite() { remaining.max(1.0) as usize } else { 1 };
+        (done + remaining).min(self.max_iterations)
+    }
+
+    /// Returns true if the calculation starts from the last pagerank vector.
+    pub fn get_warm_start(&self) -> bool {
+        self.warm_start
+    }
+
+    /// Specifies whether pagerank() starts from the pagerank vector of the 
+    /// last calculation, if it is still there and has an element per node, 
+    /// instead of the uniform distribution, so that it converges faster 
+    /// after small changes of the graph. Adding links between existing 
+    /// nodes keeps the vector, and remove_node() keeps the pagerank of the
+    /// other nodes; after nodes are added, or the graph is changed in other
+    /// ways, the calculation starts from the uniform distribution again. It
+    /// is off by default.
+    pub fn set_warm_start(&mut self, w: bool) {
+        self.warm_start = w;
+    }
+
+    /// Returns true if the difference of each iteration is recorded.
+    pub fn get_record_history(&self) -> bool {
+        self.record_history
+    }
+
+    /// Specifies whether pagerank() records the difference between 
+    /// successive pagerank vectors after each iteration, to be returned by 
+    /// get_convergence_history(). Nothing is recorded by default.
+    pub fn set_record_history(&mut self, r: bool) {
+        self.record_history = r;
+    }
+
+    /// Returns the outcome of the last pagerank calculation, if any.
+    pub fn get_last_result(&self) -> Option<PagerankResult> {
+        self.last_result
+    }
+
+    /// Returns true if the last pagerank calculation converged, false if it 
+    /// stopped at the maximum number of iterations or has not been performed.
+    pub fn did_converge(&self) -> bool {
+        self.last_result.is_some_and(|r| r.converged)
+    }
+
+    /// Returns the pagerank vector of the hyperlink matrix.
+    /// 
+    /// Deprecated: use pagerank_scores(), which returns a slice.
+    #[deprecated(note = "use pagerank_scores() instead")]
+    pub fn get_pagerank(&self) -> &Vec<PrFloat> {
+        &self.pr
+    }
+
+    /// Returns the pagerank vector of the hyperlink matrix, by node index.
+    /// It is empty until pagerank() is called.
+    pub fn pagerank_scores(&self) -> &[PrFloat] {
+        &self.pr
+    }
+
+    /// Returns an iterator over the name and pagerank of each node, in index
+    /// order.
+    pub fn iter_ranks(&self) -> impl Iterator<Item = (String, PrFloat)> + '_ {
+        self.pr
+            .iter()
+            .enumerate()
+            .map(|(i, pr)| (self.get_node_name(i), *pr))
+    }
+
+    /// Returns the pagerank of each node keyed by node name; for numeric 
+    /// input the names are the indices.
+    pub fn pagerank_map(&self) -> HashMap<String, PrFloat> {
+        self.iter_ranks().collect()
+    }
+
+    /// Compares two nodes by their pagerank in the given order. Ties are 
+    /// broken by the node names in lexicographic order, or for numeric 
+    /// input, by the node indices in increasing order, whatever the order of 
+    /// the pageranks, so that sorting is deterministic.
+    fn compare_ranks(&self, a: usize, b: usize, order: SortOrder) -> Ordering {
+        let by_rank = match order {
+            SortOrder::Ascending => self.pr[a].total_cmp(&self.pr[b]),
+            SortOrder::Descending => self.pr[b].total_cmp(&self.pr[a]),
+        };
+        by_rank.then_with(|| self.compare_nodes(a, b))
+    }
+
+    /// Compares the nodes with indices a and b by name, or by index for 
+    /// numeric input, to break ties between them. Nodes without a name 
+    /// compare by their index as a string, as get_node_name() names them.
+    fn compare_nodes(&self, a: usize, b: usize) -> Ordering {
+        if self.numeric {
+            return a.cmp(&b);
+        }
+        let name = |k: usize| match self.idx_to_nodes.get(&k) {
+            Some(name) => Cow::Borrowed(name.as_str()),
+            None => Cow::Owned(k.to_string()),
+        };
+        name(a).cmp(&name(b))
+    }
+
+    /// Returns the k nodes with the highest pagerank, in descending order of 
+    /// pagerank. Ties are broken as by write_pagerank_sorted(), so the result
+    /// is deterministic.
+    pub fn top_k(&self, k: usize) -> Vec<(String, PrFloat)> {
+        self.top_k_of((0..self.pr.len()).collect(), k)
+    }
+
+    /// Returns the k nodes with the highest pagerank among those whose name
+    /// pred returns true for, in the order of top_k(). For numeric input 
+    /// the names are the labels, or else the indices.
+    pub fn top_k_filtered<F>(&self, k: usize, pred: F) -> Vec<(String, PrFloat)>
+        where F: Fn(&str) -> bool
+    {
+        let indices = (0..self.pr.len())
+            .filter(|&i| pred(&self.get_node_name(i)))
+            .collect();
+        self.top_k_of(indices, k)
+    }
+
+    /// Returns the k nodes with the highest pagerank among the given ones, 
+    /// in descending order of pagerank.
+    fn top_k_of(&self, indices: Vec<usize>, k: usize) -> Vec<(String, PrFloat)> {
+        self.ranked_indices(indices, k, SortOrder::Descending)
+            .into_iter()
+            .map(|i| (self.get_node_name(i), self.pr[i]))
+            .collect()
+    }
+
+    /// Returns the first k of the given nodes, sorted by pagerank in the 
+    /// given order, with ties broken as by compare_ranks(). Only the first
+    /// k are sorted.
+    fn ranked_indices(&self, mut indices: Vec<usize>, k: usize, order: SortOrder) -> Vec<usize> {
+        let cmp = |a: &usize, b: &usize| self.compare_ranks(*a, *b, order);
+
+        if k < indices.len() {
+            indices.select_nth_unstable_by(k, cmp);
+            indices.truncate(k);
+        }
+        indices.sort_by(cmp);
+        indices
+    }
+
+    /// Returns all the nodes with their pagerank, in descending order of 
+    /// pagerank, with ties broken as by top_k(). For numeric input the 
+    /// names are the labels, or else the indices.
+    pub fn ranked_nodes(&self) -> Vec<(String, PrFloat)> {
+        self.top_k(self.pr.len())
+    }
+
+    /// Returns the name of the node with the given index. If the nodes are 
+    /// numeric the name is its label, if labels were loaded with 
+    /// load_labels(&PathBuf), or else the string representation of the 
+    /// number. if the nodes are not numeric, the name is the original node 
+    /// name as it was input from read_file(&str), or for a node added by 
+    /// index without a name, the string representation of the index.
+    pub fn get_node_name(&self, index: usize) -> String {
+        match self.idx_to_nodes.get(&index) {
+            Some(name) => name.to_string(),
+            None => index.to_string(),
+        }
+    }
+
+    /// Returns the index of the node with the given name. If the nodes are 
+    /// numeric the name is looked up in the labels, and otherwise parsed as
+    /// the index.
+    fn get_node_index(&self, name: &str) -> Option<usize> {
+        let key = self.node_key(name);
+        if self.numeric {
+            self.nodes_to_idx.get(key.as_ref()).copied()
+                .or_else(|| name.parse().ok())
+                .filter(|idx| *idx < self.rows.len())
+        } else {
+            self.nodes_to_idx.get(key.as_ref()).copied()
+        }
+    }
+
+    /// Returns the pagerank of the node with the given name, or None if the 
+    /// node is unknown or the pagerank has not been calculated.
+    pub fn get_rank_by_name(&self, name: &str) -> Option<PrFloat> {
+        self.get_node_index(name).and_then(|idx| self.pr.get(idx).copied())
+    }
+
+    /// Returns the mapping from node indices to node names. Numeric input 
+    /// is read without building the mapping, so for numeric input it only 
+    /// holds the labels loaded by load_labels(&PathBuf), and get_node_name()
+    /// uses the indices as names for the nodes without a label.
+    pub fn get_mapping(&self) -> &HashMap<usize, String> {
+        &self.idx_to_nodes
+    }
+
+    /// Returns the pagerank damping factor.
+    pub fn get_alpha(&self) -> PrFloat {
+        self.alpha
+    }
+
+    /// Sets the pagerank damping factor, which must be in [0, 1).
+    /// 
+    /// Returns an error, leaving the damping factor unchanged, if it is not.
+    pub fn set_alpha(&mut self, a: PrFloat) -> Result<(), PagerankError> {
+        if !(0.0..1.0).contains(&a) {
+            return Err(PagerankError::InvalidAlpha(a));
+        }
+        self.alpha = a;
+        Ok(())
+    }
+
+    /// Returns the teleport probability: the weight of the jump to a random
+    /// node (or to the personalization vector) in each iteration. Unless it
+    /// was set, it is 1 - alpha, as in standard PageRank.
+    pub fn get_teleport(&self) -> PrFloat {
+        self.teleport.unwrap_or(1.0 - self.alpha)
+    }
+
+    /// Sets a teleport probability independent of the damping factor, which
+    /// must be in (0, 1]. The link-following part of each iteration keeps 
+    /// the weight alpha; if alpha and the teleport probability do not sum 
+    /// to one, the pagerank vector is rescaled to sum to one after each 
+    /// iteration, which spreads the remaining mass in proportion to the 
+    /// pagerank of the nodes.
+    /// 
+    /// Returns an error, leaving the teleport probability unchanged, if it 
+    /// is not in (0, 1].
+    pub fn set_teleport(&mut self, beta: PrFloat) -> Result<(), PagerankError> {
+        if !(beta > 0.0 && beta <= 1.0) {
+            return Err(PagerankError::InvalidTeleport(beta));
+        }
+        self.teleport = Some(beta);
+        Ok(())
+    }
+
+    /// Restores the standard teleport probability of 1 - alpha.
+    pub fn clear_teleport(&mut self) {
+        self.teleport = None;
+    }
+
+    /// Returns the maximum number of iterations that the pagerank algorithm 
+    /// will perform.
+    pub fn get_max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Sets the maximum number of iterations that the pagerank algorithm 
+    /// will perform, which must be positive.
+    /// 
+    /// Returns an error, leaving the maximum unchanged, if it is zero.
+    pub fn set_max_iterations(&mut self, i: usize) -> Result<(), PagerankError> {
+        if i == 0 {
+            return Err(PagerankError::InvalidMaxIterations);
+        }
+        self.max_iterations = i;
+        Ok(())
+    }
+
+    /// Returns the number of iterations that the pagerank algorithm performs
+    /// regardless of convergence, if it was set.
+    pub fn get_fixed_iterations(&self) -> Option<usize> {
+        self.fixed_iterations
+    }
+
+    /// Sets the number of iterations that the pagerank algorithm performs. 
+    /// With Some(n) exactly n iterations are performed, without checking 
+    /// the convergence criterion or the maximum number of iterations; with 
+    /// None the iterations stop on convergence, as usual.
+    pub fn set_fixed_iterations(&mut self, n: Option<usize>) {
+        self.fixed_iterations = n;
+    }
+
+    /// Returns the time after which the pagerank algorithm stops iterating,
+    /// if it was set.
+    pub fn get_time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    /// Sets the time after which the pagerank algorithm stops iterating. 
+    /// The time is checked before each iteration; once it is exceeded, the
+    /// calculation stops with the pagerank vector of the last iteration, 
+    /// and its result is marked as timed out and not converged. With None 
+    /// there is no limit on the time.
+    pub fn set_time_budget(&mut self, budget: Option<Duration>) {
+        self.time_budget = budget;
+    }
+
+    /// Returns the value that is used to determine convergence of the
+    /// pagerank calculation algorithm.
+    pub fn get_convergence(&self) -> PrFloat {
+        self.convergence
+    }
+
+    /// Sets the value that is used to determine convergence of the
+    /// pagerank calculation algorithm, which must be positive.
+    /// 
+    /// Returns an error, leaving the value unchanged, if it is not.
+    pub fn set_convergence(&mut self, c: PrFloat) -> Result<(), PagerankError> {
+        if c.is_nan() || c <= 0.0 {
+            return Err(PagerankError::InvalidConvergence(c));
+        }
+        self.convergence = c;
+        Ok(())
+    }
+
+    /// Returns the norm used to measure the difference between successive 
+    /// pagerank vectors.
+    pub fn get_convergence_norm(&self) -> ConvergenceNorm {
+        self.convergence_norm
+    }
+
+    /// Sets the norm used to measure the difference between successive 
+    /// pagerank vectors, which is compared against the convergence value.
+    /// The default is the L1 norm.
+    pub fn set_convergence_norm(&mut self, n: ConvergenceNorm) {
+        self.convergence_norm = n;
+    }
+
+    /// Returns the personalization vector, i.e. the teleport probability of 
+    /// each node, if one has been set.
+    pub fn get_personalization(&self) -> Option<&HashMap<usize, f64>> {
+        self.personalization.as_ref()
+    }
+
+    /// Sets the personalization vector used for personalized pagerank: 
+    /// instead of teleporting uniformly to any node, teleportation goes to 
+    /// the given nodes (by index) in proportion to their weights. The weights 
+    /// are normalized to sum to one; nodes without a weight get zero.
+    /// 
+    /// Returns an error if a weight is negative or not finite, or if the 
+    /// weights sum to zero.
+    pub fn set_personalization(
+        &mut self,
+        weights: HashMap<usize, f64>,
+    ) -> Result<(), PagerankError> {
+        if weights.values().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(PagerankError::InvalidPersonalization);
+        }
+        let sum: f64 = weights.values().sum();
+        if sum <= 0.0 {
+            return Err(PagerankError::InvalidPersonalization);
+        }
+
+        let normalized = weights.into_iter().map(|(k, w)| (k, w / sum)).collect();
+        self.personalization = Some(normalized);
+        Ok(())
+    }
+
+    /// Removes the personalization vector, so that teleportation is uniform.
+    pub fn clear_personalization(&mut self) {
+        self.personalization = None;
+    }
+
+    /// Sets a function to be called at the end of each pagerank iteration, 
+    /// e.g. to monitor the convergence of the calculation.
+    pub fn set_iteration_callback(&mut self, f: IterationCallback) {
+        self.iteration_callback = Some(f);
+    }
+
+    /// Removes the function set by set_iteration_callback().
+    pub fn clear_iteration_callback(&mut self) {
+        self.iteration_callback = None;
+    }
+
+    /// Sets a function to be called with the progress of reading a graph, 
+    /// e.g. to drive a progress bar. The progress is then no longer printed
+    /// to stdout.
+    pub fn set_progress_callback(&mut self, f: ProgressCallback) {
+        self.progress_callback = Some(f);
+    }
+
+    /// Removes the function set by set_progress_callback().
+    pub fn clear_progress_callback(&mut self) {
+        self.progress_callback = None;
+    }
+
+    /// Returns true if the durations of reading and of the pagerank 
+    /// calculation are measured.
+    pub fn get_timing(&self) -> bool {
+        self.timing
+    }
+
+    /// Specifies whether the wall-clock durations of reading a graph and of
+    /// calculating its pagerank are measured, for get_read_time() and 
+    /// get_pagerank_time(). Nothing is measured when it is off.
+    pub fn set_timing(&mut self, t: bool) {
+        self.timing = t;
+    }
+
+    /// Returns the duration of the last read of a graph, if timing was on 
+    /// and the read succeeded. The compressed hyperlink matrix is built by 
+    /// each calculation, so it counts in get_pagerank_time() instead.
+    pub fn get_read_time(&self) -> Option<Duration> {
+        self.read_time
+    }
+
+    /// Returns the duration of the last pagerank calculation, if timing was
+    /// on.
+    pub fn get_pagerank_time(&self) -> Option<Duration> {
+        self.pagerank_time
+    }
+
+    /// Returns the number of lines read between progress reports.
+    pub fn get_progress_interval(&self) -> usize {
+        self.progress_interval
+    }
+
+    /// Sets the number of lines read between progress reports. With zero 
+    /// the progress is reported only at the end of each input.
+    pub fn set_progress_interval(&mut self, n: usize) {
+        self.progress_interval = n;
+    }
+
+    /// Returns true when tracing output is enabled, false otherwise.
+    pub fn get_trace(&self) -> bool {
+        self.verbosity == Verbosity::Trace
+    }
+
+    /// Sets tracing output, as set_verbosity() does with the trace level, 
+    /// or when disabled, with the info level.
+    pub fn set_trace(&mut self, t: bool) {
+        self.set_verbosity(if t { Verbosity::Trace } else { Verbosity::Info });
+    }
+
+    /// Returns how much the table reports about its work.
+    pub fn get_verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Sets how much the table reports about its work. The debug and trace
+    /// levels raise the maximum log level to theirs, so that their messages
+    /// reach the installed logger; the silent level suppresses the progress
+    /// output as well. The default is the info level.
+    pub fn set_verbosity(&mut self, v: Verbosity) {
+        self.verbosity = v;
+        let level = match v {
+            Verbosity::Trace => LevelFilter::Trace,
+            Verbosity::Debug => LevelFilter::Debug,
+            _ => LevelFilter::Off,
+        };
+        if log::max_level() < level {
+            log::set_max_level(level);
+        }
+    }
+
+    /// Returns true if the progress output while reading is suppressed.
+    pub fn get_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /// Specifies whether the progress output ("read <n> lines, <m> 
+    /// vertices") printed to stdout while reading the graph is suppressed.
+    pub fn set_quiet(&mut self, q: bool) {
+        self.quiet = q;
+    }
+
+    /// Returns true if the graph data to be read by read_file(sting) are in 
+    /// numeric form (e.g., integer values starting from zero) or in string form.
+    pub fn get_numeric(&self) -> bool {
+        self.numeric
+    }
+
+    /// Specifies whether the graph data to be read by read_file(sting) 
+    /// are in numeric form (e.g., integer values starting from zero) 
+    /// or in string form. Numeric vertices are used as indices directly, 
+    /// without keeping a mapping between names and indices, which saves 
+    /// memory on large graphs.
+    pub fn set_numeric(&mut self, n: bool) {
+        self.numeric = n;
+    }
+
+    /// Returns the largest vertex index accepted from the input, if any.
+    pub fn get_max_index(&self) -> Option<usize> {
+        self.max_index
+    }
+
+    /// Sets the largest vertex index accepted from numeric input, and from 
+    /// the dimensions of Matrix Market input, so that a mistyped huge index
+    /// is reported as an error, or skipped in lenient mode, instead of 
+    /// growing the tables to its size. With None, the default, any index is
+    /// accepted.
+    pub fn set_max_index(&mut self, max: Option<usize>) {
+        self.max_index = max;
+    }
+
+    /// Returns true if the edges of the graph are weighted.
+    pub fn get_weighted(&self) -> bool {
+        self.weighted
+    }
+
+    /// Specifies whether the edges of the graph are weighted. In weighted 
+    /// mode each line of the graph data file may have a third column with 
+    /// the (non-negative) weight of the edge, which defaults to one, and the
+    /// pagerank of a node is distributed to its outgoing links in proportion 
+    /// to their weights. Edges already added get a weight of one, unless 
+    /// multi-edges are allowed, in which case they keep their multiplicity.
+    pub fn set_weighted(&mut self, w: bool) {
+        let kept_before = self.keeps_weights();
+        self.weighted = w;
+        self.update_weights(kept_before);
+    }
+
+    /// Returns true if the graph is undirected.
+    pub fn get_undirected(&self) -> bool {
+        self.undirected
+    }
+
+    /// Specifies whether the graph is undirected. In undirected mode each 
+    /// edge between two different nodes adds the arcs in both directions, 
+    /// so that the graph data need not contain each edge twice; self-loops
+    /// are added once. The mode applies to the edges added after it is set.
+    pub fn set_undirected(&mut self, u: bool) {
+        self.undirected = u;
+    }
+
+    /// Returns true if edges whose reverse was read before are skipped.
+    pub fn get_dedup_symmetric(&self) -> bool {
+        self.dedup_symmetric
+    }
+
+    /// Specifies whether an edge of the graph data file whose reverse was 
+    /// read before, from any of the files read together, is skipped, for 
+    /// data that lists each undirected edge in both directions. Only the 
+    /// direction read first is kept, unless the graph is undirected, in 
+    /// which case it adds both directions once, whatever the multi-edge 
+    /// mode. Repeats of the same edge are not affected. The edges are 
+    /// remembered while the graph is read, which takes memory proportional 
+    /// to their number.
+    pub fn set_dedup_symmetric(&mut self, d: bool) {
+        self.dedup_symmetric = d;
+    }
+
+    /// Returns the number of edges skipped by the last read because their 
+    /// reverse was read before, when symmetric duplicates are removed.
+    pub fn get_symmetric_duplicates(&self) -> usize {
+        self.symmetric_duplicates
+    }
+
+    /// Returns the number of edges skipped by the last read because of the 
+    /// node whitelist or blacklist.
+    pub fn get_filtered_edges(&self) -> usize {
+        self.filtered_edges
+    }
+
+    /// Returns true if the edges are added in reverse.
+    pub fn get_reverse(&self) -> bool {
+        self.reverse
+    }
+
+    /// Specifies whether each edge from a node to another adds the arc from
+    /// the other node to the first instead, so that the pagerank is that of
+    /// the transposed graph, which ranks highest the nodes that link to 
+    /// important ones. The mode applies to the edges added after it is set.
+    pub fn set_reverse(&mut self, r: bool) {
+        self.reverse = r;
+    }
+
+    /// Returns the method used to calculate each pagerank iteration.
+    pub fn get_iteration_method(&self) -> IterationMethod {
+        self.iteration_method
+    }
+
+    /// Sets the method used to calculate each pagerank iteration: Jacobi 
+    /// (the default), Gauss-Seidel, which updates the pagerank vector in
+    /// place, or Push, which scatters the pagerank of each node along its 
+    /// outgoing links. Dangling nodes have no links to push along, so with
+    /// Push their pagerank is spread according to the dangling policy, from
+    /// the previous vector, as with Jacobi; both methods compare successive
+    /// vectors in the convergence norm, so they take the same number of 
+    /// iterations to converge to the same pagerank.
+    pub fn set_iteration_method(&mut self, m: IterationMethod) {
+        self.iteration_method = m;
+    }
+
+    /// Returns how the pagerank of dangling nodes is distributed.
+    pub fn get_dangling_policy(&self) -> DanglingPolicy {
+        self.dangling_policy
+    }
+
+    /// Sets how the pagerank of dangling nodes is distributed: uniformly to
+    /// all nodes (the default), like teleportation, or not at all.
+    pub fn set_dangling_policy(&mut self, p: DanglingPolicy) {
+        self.dangling_policy = p;
+    }
+
+    /// Returns true if isolated nodes are left out of the uniform teleport.
+    pub fn get_ignore_isolated(&self) -> bool {
+        self.ignore_isolated
+    }
+
+    /// Specifies whether isolated nodes, without any incoming or outgoing 
+    /// links, are left out of the uniform distribution of the teleport and
+    /// of the pagerank of dangling nodes, which are then shared only by the
+    /// nodes with links. This changes the model: the random surfer never 
+    /// jumps to an isolated node, so isolated nodes, e.g. unused indices of
+    /// numeric input, get a pagerank of zero instead of diluting that of the
+    /// other nodes. A personalization vector still applies as given.
+    pub fn set_ignore_isolated(&mut self, i: bool) {
+        self.ignore_isolated = i;
+    }
+
+    /// Returns the policy for self-loops.
+    pub fn get_self_loops(&self) -> SelfLoopPolicy {
+        self.self_loops
+    }
+
+    /// Sets how arcs from a node to itself are handled: kept like any other 
+    /// arc (the default), dropped, or counted as links whose pagerank is 
+    /// distributed to all nodes. The policy applies to the edges added 
+    /// after it is set.
+    pub fn set_self_loops(&mut self, p: SelfLoopPolicy) {
+        self.self_loops = p;
+    }
+
+    /// Returns true if repeated edges are counted instead of ignored.
+    pub fn get_allow_multi_edges(&self) -> bool {
+        self.allow_multi_edges
+    }
+
+    /// Specifies whether repeated edges between the same nodes are counted. 
+    /// By default (false) an edge that is added again is ignored. When 
+    /// multi-edges are allowed, each repetition adds its weight (one, 
+    /// unless in weighted mode) to the link, and the pagerank of a node is 
+    /// distributed to its outgoing links in proportion to their total 
+    /// weight, so that a doubled edge counts twice. Edges already added 
+    /// count once.
+    pub fn set_allow_multi_edges(&mut self, m: bool) {
+        let kept_before = self.keeps_weights();
+        self.allow_multi_edges = m;
+        self.update_weights(kept_before);
+    }
+
+    /// Returns the delimeter used in the graph data file. The data
+    /// file is composed of lines with the following format:
+    /// <from><delim><to>
+    /// where from and to are the two graph vertices (can be either strings or
+    /// integers) and delim is the delimiter.
+    pub fn get_delim(&self) -> &str {
+        &self.delim
+    }
+
+    /// Sets the delimited to be used for reading the graph data file.
+    pub fn set_delim(&mut self, d: &str) {
+        self.delim = d.to_string();
+        self.extra_delims.clear();
+    }
+
+    /// Returns the delimiters accepted in the graph data file, the one 
+    /// returned by get_delim() first.
+    pub fn get_delims(&self) -> Vec<&str> {
+        iter::once(&self.delim).chain(&self.extra_delims).map(String::as_str).collect()
+    }
+
+    /// Sets several delimiters for reading the graph data file, for files 
+    /// that mix them, e.g. with tabs in some lines and spaces in others. 
+    /// Each line is split at the first of them found in it. The first 
+    /// delimiter is the one returned by get_delim() and used for output. An
+    /// empty slice leaves the delimiters unchanged.
+    pub fn set_delims(&mut self, delims: &[&str]) {
+        if let Some((first, rest)) = delims.split_first() {
+            self.delim = first.to_string();
+            self.extra_delims = rest.iter().map(|d| d.to_string()).collect();
+        }
+    }
+
+    /// Returns true if the fields of the graph data file are separated by 
+    /// whitespace instead of the delimiter.
+    pub fn get_whitespace(&self) -> bool {
+        self.whitespace
+    }
+
+    /// Specifies whether the fields of each line of the graph data file are
+    /// separated by any run of spaces and tabs, instead of the delimiter, 
+    /// which is then ignored. Leading and trailing whitespace is removed.
+    pub fn set_whitespace(&mut self, w: bool) {
+        self.whitespace = w;
+    }
+
+    /// Returns true if double quotes around the fields of the graph data 
+    /// file are removed.
+    pub fn get_trim_quotes(&self) -> bool {
+        self.trim_quotes
+    }
+
+    /// Specifies whether double quotes around the vertex names and weights 
+    /// of the graph data file are removed, as in CSV files where 
+    /// "a","b" is an edge from a to b. The delimiter may then appear in 
+    /// quoted names, so that "a,b","c" is an edge from a,b to c.
+    pub fn set_trim_quotes(&mut self, q: bool) {
+        self.trim_quotes = q;
+    }
+
+    /// Returns true if node names that differ only in case are the same 
+    /// node.
+    pub fn get_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Specifies whether node names that differ only in case, such as Apple
+    /// and apple, are the same node, for string input and for the labels 
+    /// of numeric input. This changes how edges are merged: edges between 
+    /// casing variants of the same names are repeats of one edge, ignored 
+    /// unless multi-edges are allowed, and an edge between two variants of 
+    /// one name is a self-loop. A node is shown by the name it was first 
+    /// read with, and names are looked up, e.g. by get_rank_by_name(), whatever 
+    /// their case. It must be set before the graph is read.
+    pub fn set_case_insensitive(&mut self, c: bool) {
+        self.case_insensitive = c;
+    }
+
+    /// Returns the prefix of the comment lines of the graph data file.
+    pub fn get_comment_prefix(&self) -> &str {
+        &self.comment_prefix
+    }
+
+    /// Sets the prefix of the comment lines of the graph data file, "#" by
+    /// default. Lines starting with the prefix, after any leading 
+    /// whitespace, are not read, and are not counted by get_skipped_lines().
+    /// An empty prefix means there are no comment lines.
+    pub fn set_comment_prefix(&mut self, p: &str) {
+        self.comment_prefix = p.to_string();
+    }
+
+    /// Returns the number of header lines of the graph data.
+    pub fn get_skip_header(&self) -> usize {
+        self.skip_header
+    }
+
+    /// Sets the number of lines at the start of each graph data file, or of
+    /// the standard input, that are a header and are not read, like comment 
+    /// lines. Matrix Market files have their own header and are not affected.
+    pub fn set_skip_header(&mut self, n: usize) {
+        self.skip_header = n;
+    }
+
+    /// Returns true if lines without the delimiter are treated as errors.
+    pub fn get_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Specifies whether non-blank lines without the delimiter are treated 
+    /// as errors by read_file(&PathBuf), instead of being skipped.
+    pub fn set_strict(&mut self, s: bool) {
+        self.strict = s;
+    }
+
+    /// Returns true if lines with invalid numeric vertices are skipped.
+    pub fn get_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Specifies whether lines of numeric input with a vertex that is not a
+    /// valid index are skipped by read_file(&PathBuf), and counted by 
+    /// get_invalid_lines(), instead of being treated as errors. Each skipped 
+    /// line is logged as a warning.
+    pub fn set_lenient(&mut self, l: bool) {
+        self.lenient = l;
+    }
+
+    /// Returns true if files are scanned to count their vertices before 
+    /// they are read.
+    pub fn get_count_first(&self) -> bool {
+        self.count_first
+    }
+
+    /// Specifies whether read_file(&PathBuf) reads a file twice, first to 
+    /// count its vertices and reserve space for them, so that the tables do 
+    /// not have to grow while the graph is built. The standard input cannot 
+    /// be read twice, so read_stdin() always grows the tables as needed.
+    pub fn set_count_first(&mut self, c: bool) {
+        self.count_first = c;
+    }
+
+    /// Returns the number of non-blank lines without the delimiter that were
+    /// skipped by the last read.
+    pub fn get_skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+
+    /// Returns the number of lines with invalid numeric vertices that were
+    /// skipped by the last read in lenient mode.
+    pub fn get_invalid_lines(&self) -> usize {
+        self.invalid_lines
+    }
+
+    /// Returns the number of decimal places of the pagerank values in the 
+    /// text output, or zero for full precision.
+    pub fn get_output_precision(&self) -> usize {
+        self.output_precision
+    }
+
+    /// Sets the number of decimal places of the pagerank values output by 
+    /// write_pagerank_v() and write_pagerank_sorted(), and returned by 
+    /// format_rank(). Zero, the default, outputs them with full precision. 
+    /// The JSON output always has full precision.
+    pub fn set_output_precision(&mut self, p: usize) {
+        self.output_precision = p;
+    }
+
+    /// Formats a pagerank value for the text output, with the number of 
+    /// decimal places of the output precision, if any.
+    pub fn format_rank(&self, rank: PrFloat) -> String {
+        match self.output_precision {
+            0 => rank.to_string(),
+            p => format!("{:.*}", p, rank),
+        }
+    }
+
+    /// Outputs the parameters of the pagerank algorithm to the
+    /// given output stream. The parameters are:
+    /// - the damping factor (alpha)
+    /// - the convergence criterion (convergence)
+    /// - the maximum number of iterations (max iterations)
+    /// - whether numeric or string input is expected (numeric)
+    /// - the delimiter for separating the two vertices in each line of the
+    ///   input file (delim)
+    pub fn write_params<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let delim = if self.whitespace {
+            "whitespace".to_string()
+        } else {
+            self.get_delims().iter().map(|d| format!("'{}'", d)).collect::<Vec<_>>().join(", ")
+        };
+        writeln!(w, "alpha = {} convergence = {} max_iterations = {} numeric = {} delimiter = {}", 
+            self.alpha, self.convergence, self.max_iterations, self.numeric, delim)?;
+        if let Some(beta) = self.teleport {
+            writeln!(w, "teleport = {}", beta)?;
+        }
+        if let Some(n) = self.fixed_iterations {
+            writeln!(w, "fixed_iterations = {}", n)?;
+        }
+        if let Some(budget) = self.time_budget {
+            writeln!(w, "time_budget = {:?}", budget)?;
+        }
+        Ok(())
+    }
+
+    /// Outputs the parameters of the pagerank algorithm to stdout, as 
+    /// write_params() does.
+    pub fn print_params(&self) {
+        Self::print_with(|w| self.write_params(w));
+    }
+
+    /// Outputs the hyperlink table to the given output stream.
+    pub fn write_table<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (i, cr) in self.rows.iter().enumerate() {
+            write!(w, "{}:[ ", i)?;
+            for cc in cr {
+                if self.numeric {
+                    write!(w, "{} ", cc)?;
+                } else {
+                    write!(w, "{} ", self.get_node_name(*cc))?;
+                }
+            }
+            writeln!(w, "]")?;
+        }
+        Ok(())
+    }
+
+    /// Outputs the hyperlink table to stdout.
+    pub fn print_table(&self) {
+        Self::print_with(|w| self.write_table(w));
+    }
+
+    /// Outputs the number of outgoing links for each vertex of the 
+    /// hyperlink table to the given output stream.
+    pub fn write_outgoing<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "[ ")?;
+        for cn in &self.num_outgoing {
+            write!(w, "{} ", cn)?;
+        }
+        writeln!(w, "]")
+    }
+
+    /// Outputs the number of outgoing links for each vertex of the 
+    /// hyperlink table to stdout.
+    pub fn print_outgoing(&self) {
+        Self::print_with(|w| self.write_outgoing(w));
+    }
+
+    /// Outputs the edges of the graph to the given output stream, one per 
+    /// line as <from><delim><to>, in the format read by read_file(&PathBuf)
+    /// with the current delimiter, or a space in whitespace mode. Nodes are
+    /// output by name, or for numeric input, by index, even if labels were 
+    /// loaded, so that the output can be read back. In weighted or 
+    /// multi-edge mode the total weight of each edge is output in a third 
+    /// column.
+    pub fn write_edges<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let delim = if self.whitespace { " " } else { self.delim.as_str() };
+        let node = |k: usize| if self.numeric { k.to_string() } else { self.get_node_name(k) };
+        for (to, cr) in self.rows.iter().enumerate() {
+            let to_name = node(to);
+            for (j, &from) in cr.iter().enumerate() {
+                write!(w, "{}{}{}", node(from), delim, to_name)?;
+                if self.keeps_weights() {
+                    write!(w, "{}{}", delim, self.weights[to][j])?;
+                }
+                writeln!(w)?;
+            }
+        }
+        for (k, weight) in self.loop_weights.iter().enumerate() {
+            if let Some(weight) = weight {
+                let name = node(k);
+                write!(w, "{}{}{}", name, delim, name)?;
+                if self.keeps_weights() {
+                    write!(w, "{}{}", delim, weight)?;
+                }
+                writeln!(w)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Outputs the edges of the graph to stdout, as write_edges() does.
+    pub fn print_edges(&self) {
+        Self::print_with(|w| self.write_edges(w));
+    }
+
+    /// Outputs the pagerank vector to the given output stream, as a single
+    /// line with the values of the vector and the running sum.
+    pub fn write_pagerank<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "{}", self.format_pagerank())
+    }
+
+    /// Prints the pagerank vector to stdout, as write_pagerank() does.
+    pub fn print_pagerank(&self) {
+        Self::print_with(|w| self.write_pagerank(w));
+    }
+
+    /// Formats the pagerank vector as output by write_pagerank(): 
+    /// (<length>) [ <value> <value> ... ] <sum>
+    fn format_pagerank(&self) -> String {
+        let mut sum: PrFloat = 0.0;
+
+        let mut s = format!("({}) [ ", self.pr.len());
+        for cr in &self.pr {
+            s += &format!("{:10} ", cr);
+            sum += *cr;
+        }
+        
+        s += &format!("] {}", sum);
+        s
+    }
+
+    /// Outputs the pageranks vector to the given output stream in a more 
+    /// verbose way than write_pagerank(): it substitutes string vertex names
+    /// for numeric IDs, if available. The output format is a series of lines:
+    /// <node> = <pagerank value> followed by a line:
+    /// s = <sum> where <sum> is the sum of the pagerank values, which
+    /// should be equal to one.
+    pub fn write_pagerank_v<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut i = 0;
+        let num_rows = self.pr.len();
+        let mut sum = 0.0;
+
+        while i < num_rows {
+            writeln!(w, "{} = {}", self.get_node_name(i), self.format_rank(self.pr[i]))?;
+            sum += self.pr[i];
+
+            i += 1;
+        }
+
+        writeln!(w, "s = {} ", self.format_rank(sum))
+    }
+
+    /// Outputs the pageranks vector to stdout, as write_pagerank_v() does.
+    pub fn print_pagerank_v(&self) {
+        Self::print_with(|w| self.write_pagerank_v(w));
+    }
+
+    /// Outputs the pagerank vector in the same format as write_pagerank_v(),
+    /// but with the nodes sorted by pagerank in the given order. Ties are 
+    /// broken by node name in lexicographic order, or for numeric input, by 
+    /// node index.
+    pub fn write_pagerank_sorted<W: Write>(
+        &self,
+        w: &mut W,
+        order: SortOrder,
+    ) -> io::Result<()> {
+        let num_rows = self.pr.len();
+        let indices = self.ranked_indices((0..num_rows).collect(), num_rows, order);
+
+        let mut sum = 0.0;
+        for i in indices {
+            writeln!(w, "{} = {}", self.get_node_name(i), self.format_rank(self.pr[i]))?;
+            sum += self.pr[i];
+        }
+
+        writeln!(w, "s = {} ", self.format_rank(sum))
+    }
+
+    /// Outputs the pagerank vector to stdout, as write_pagerank_sorted() does.
+    pub fn print_pagerank_sorted(&self, order: SortOrder) {
+        Self::print_with(|w| self.write_pagerank_sorted(w, order));
+    }
+
+    /// Outputs the pagerank vector to the given output stream as a JSON 
+    /// object, with the nodes in an array of {"node": <name>, "rank": <value>} 
+    /// objects and the sum of their pagerank values:
+    /// {"ranks": [...], "sum": <sum>}
+    /// If k is given, only the top k nodes, as returned by top_k(), are output.
+    pub fn write_pagerank_json<W: Write>(&self, w: &mut W, k: Option<usize>) -> io::Result<()> {
+        let ranks: Vec<(String, PrFloat)> = match k {
+            Some(k) => self.top_k(k),
+            None => self.iter_ranks().collect(),
+        };
+
+        let mut sum = 0.0;
+        write!(w, "{{\"ranks\": [")?;
+        for (i, (node, rank)) in ranks.iter().enumerate() {
+            if i > 0 {
+                write!(w, ", ")?;
+            }
+            write!(w, "{{\"node\": ")?;
+            json::write_string(w, node)?;
+            write!(w, ", \"rank\": ")?;
+            json::write_number(w, *rank)?;
+            write!(w, "}}")?;
+            sum += rank;
+        }
+        write!(w, "], \"sum\": ")?;
+        json::write_number(w, sum)?;
+        writeln!(w, "}}")
+    }
+
+    /// Outputs the graph to the given output stream as a Graphviz digraph, 
+    /// with an edge from -> to for each arc. Each node is labelled with its 
+    /// name and pagerank value, and its width is proportional to its 
+    /// pagerank value relative to the highest one.
+    pub fn write_dot<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let max_pr = self.pr.iter().cloned().fold(0.0, PrFloat::max);
+
+        writeln!(w, "digraph pagerank {{")?;
+        for i in 0..self.rows.len() {
+            let name = self.get_node_name(i);
+            let rank = self.pr.get(i).copied().unwrap_or(0.0);
+            let width = if max_pr > 0.0 {
+                0.25 + 1.75 * rank / max_pr
+            } else {
+                0.75
+            };
+            write!(w, "    ")?;
+            dot::write_id(w, &name)?;
+            write!(w, " [label=")?;
+            dot::write_id(w, &format!("{}\n{:.4}", name, rank))?;
+            writeln!(w, ", width={:.3}];", width)?;
+        }
+        for (from, to) in self.edges() {
+            write!(w, "    ")?;
+            dot::write_id(w, &self.get_node_name(from))?;
+            write!(w, " -> ")?;
+            dot::write_id(w, &self.get_node_name(to))?;
+            writeln!(w, ";")?;
+        }
+        writeln!(w, "}}")
+    }
+
+    /// Calls f with a locked stdout, panicking on failure like println!.
+    fn print_with<F>(f: F)
+        where F: FnOnce(&mut io::StdoutLock<'static>) -> io::Result<()>
+    {
+        if let Err(e) = f(&mut io::stdout().lock()) {
+            panic!("failed printing to stdout: {}", e);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the edges of t by node name, sorted.
+    fn named_edges(t: &Table) -> Vec<(String, String)> {
+        let mut edges: Vec<_> = t.edges()
+            .map(|(from, to)| (t.get_node_name(from), t.get_node_name(to)))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn pairs(edges: &[(&str, &str)]) -> Vec<(String, String)> {
+        edges.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    /// Returns a numeric table with the given edges.
+    fn numeric_graph(edges: &[(usize, usize)]) -> Table {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        for &(from, to) in edges {
+            t.add_edge_idx(from, to);
+        }
+        t
+    }
+
+    /// Asserts that a and b are equal within tol, element by element.
+    fn assert_close(a: &[PrFloat], b: &[PrFloat], tol: PrFloat) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    /// Returns the path of a file of the data directory.
+    fn data_file(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data").join(name)
+    }
+
+    /// Returns the pagerank vector of a reference file of the data 
+    /// directory, without the sum.
+    fn reference_pagerank(name: &str) -> Vec<PrFloat> {
+        std::fs::read_to_string(data_file(name))
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.split_once(" = "))
+            .filter(|(node, _)| *node != "s")
+            .map(|(_, pr)| pr.trim().parse().unwrap())
+            .collect()
+    }
+
+    /// Writes contents to a file of the temporary directory and returns its
+    /// path. The name is prefixed with the process id, so that concurrent 
+    /// runs of the tests do not collide.
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("pagerank-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Calculates the pagerank of t directly from its definition, with dense
+    /// matrices and dangling nodes linking to all nodes.
+    fn dense_pagerank(t: &Table, iterations: usize) -> Vec<PrFloat> {
+        let n = t.rows.len();
+        let alpha = t.alpha;
+        let mut pr = vec![1.0 / n as PrFloat; n];
+        for _ in 0..iterations {
+            let mut next = vec![(1.0 - alpha) / n as PrFloat; n];
+            for (from, &p) in pr.iter().enumerate() {
+                let out = t.num_outgoing[from];
+                for (to, row) in t.rows.iter().enumerate() {
+                    if out == 0 {
+                        next[to] += alpha * p / n as PrFloat;
+                    } else if row.contains(&from) {
+                        next[to] += alpha * p / out as PrFloat;
+                    }
+                }
+            }
+            pr = next;
+        }
+        pr
+    }
+
+    /// Returns the pagerank of a graph with the edge from a to b doubled.
+    fn doubled_edge(multi_edges: bool) -> Table {
+        let mut t = Table::new();
+        t.set_allow_multi_edges(multi_edges);
+        for (from, to) in [("a", "b"), ("a", "b"), ("a", "c"), ("b", "a"), ("c", "a")] {
+            t.add_edge(from, to);
+        }
+        t.pagerank().unwrap();
+        t
+    }
+
+    /// Returns the pagerank of a with a self-loop under the given policy.
+    fn self_loop_rank(policy: SelfLoopPolicy) -> PrFloat {
+        let mut t = Table::new();
+        t.set_self_loops(policy);
+        for (from, to) in [("a", "a"), ("a", "b"), ("b", "a")] {
+            t.add_edge(from, to);
+        }
+        t.pagerank().unwrap();
+        assert!(t.check_pagerank().is_ok());
+        t.get_rank_by_name("a").unwrap()
+    }
+
+    #[test]
+    fn large_source_with_small_target() {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.add_edge_idx(0, 1);
+        t.add_edge_idx(9, 1);
+
+        assert_eq!(t.num_outgoing[9], 1);
+        assert_eq!(t.rows.len(), 10);
+        assert!(t.pagerank().is_ok());
+    }
+
+    #[test]
+    fn format_pagerank_has_a_single_sum() {
+        let mut t = Table::new();
+        t.pr = vec![0.25, 0.75];
+
+        let s = t.format_pagerank();
+        assert_eq!(s, "(2) [       0.25       0.75 ] 1");
+        assert!(!s.contains("s ="));
+    }
+
+    #[test]
+    fn uniform_start_matches_the_reference_pagerank() {
+        let mut t = Table::new();
+        t.set_quiet(true);
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.read_file(&data_file("bull.txt")).unwrap();
+        t.pagerank().unwrap();
+
+        assert_close(&t.pr, &reference_pagerank("bull-pr-p.txt"), 1e-4);
+    }
+
+    #[test]
+    fn dangling_policies_give_distributions() {
+        let mut ranks = Vec::new();
+        for policy in [DanglingPolicy::Uniform, DanglingPolicy::Teleport, DanglingPolicy::Ignore] {
+            let mut t = Table::new();
+            t.set_dangling_policy(policy);
+            // c is the only dangling node
+            for (from, to) in [("a", "b"), ("a", "c"), ("b", "c"), ("d", "a")] {
+                t.add_edge(from, to);
+            }
+            assert!(t.pagerank().unwrap().converged);
+            assert!(t.check_pagerank().is_ok(), "{:?}", policy);
+            ranks.push(t.get_rank_by_name("c").unwrap());
+        }
+
+        // Without a personalization vector teleporting is uniform
+        assert!((ranks[0] - ranks[1]).abs() < 1e-6);
+        // Dropping the dangling mass instead changes the distribution
+        assert!(ranks[2] > ranks[0]);
+    }
+
+    #[test]
+    fn pagerank_matches_the_definition() {
+        // Out-degrees from 0 to 3, with 4 dangling
+        let mut t = numeric_graph(&[(0, 1), (0, 2), (0, 3), (1, 2), (2, 0), (3, 2), (3, 4)]);
+        assert!(t.pagerank().unwrap().converged);
+
+        assert_close(&t.pr, &dense_pagerank(&t, 200), 1e-4);
+    }
+
+    #[test]
+    fn reset_results_matches_a_fresh_run() {
+        let edges = [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")];
+        let mut t = Table::new();
+        for (from, to) in edges {
+            t.add_edge(from, to);
+        }
+        t.pagerank().unwrap();
+        t.set_alpha(0.5).unwrap();
+        t.reset_results();
+        let again = t.pagerank().unwrap();
+
+        let mut fresh = Table::new();
+        fresh.set_alpha(0.5).unwrap();
+        for (from, to) in edges {
+            fresh.add_edge(from, to);
+        }
+        let first = fresh.pagerank().unwrap();
+
+        assert_eq!(t.pr, fresh.pr);
+        assert_eq!(again.iterations, first.iterations);
+    }
+
+    #[test]
+    fn push_agrees_with_jacobi() {
+        // A hub with a dangling leaf
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 0), (2, 0), (2, 1)];
+        let mut jacobi = numeric_graph(&edges);
+        let mut push = numeric_graph(&edges);
+        push.set_iteration_method(IterationMethod::Push);
+
+        let jacobi_result = jacobi.pagerank().unwrap();
+        let push_result = push.pagerank().unwrap();
+        assert!(push_result.converged);
+        assert_eq!(jacobi_result.iterations, push_result.iterations);
+        assert_close(&jacobi.pr, &push.pr, 1e-9);
+    }
+
+    #[test]
+    fn isolated_padding_is_ignored() {
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3)];
+        let mut unpadded = numeric_graph(&edges);
+        unpadded.pagerank().unwrap();
+
+        let mut padded = numeric_graph(&edges);
+        padded.set_num_rows(7);
+        padded.set_ignore_isolated(true);
+        padded.pagerank().unwrap();
+
+        assert_eq!(&padded.pr[4..], &[0.0; 3]);
+        assert_close(&padded.pr[..4], &unpadded.pr, 1e-4);
+        assert!(padded.check_pagerank().is_ok());
+    }
+
+    #[test]
+    fn case_insensitive_names_collapse() {
+        let mut t = Table::new();
+        t.set_case_insensitive(true);
+        t.add_edge("Apple", "b");
+        t.add_edge("apple", "B");
+        t.add_edge("b", "c");
+
+        assert_eq!(t.num_nodes(), 3);
+        assert_eq!(t.num_edges(), 2);
+        assert_eq!(named_edges(&t), pairs(&[("Apple", "b"), ("b", "c")]));
+        t.pagerank().unwrap();
+        assert_eq!(t.get_rank_by_name("APPLE"), t.get_rank_by_name("Apple"));
+        assert!(t.get_rank_by_name("APPLE").is_some());
+    }
+
+    #[test]
+    fn mixed_delimiters_split_each_line() {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_delims(&["\t", " "]);
+        t.read_from_reader("0\t1\n1 2\n2\t0\n2 3\n".as_bytes()).unwrap();
+
+        assert_eq!(t.get_skipped_lines(), 0);
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(2, 0), (0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn merge_keeps_reversed_arcs() {
+        let mut a = Table::new();
+        a.set_reverse(true);
+        a.add_edge("x", "y");
+        let mut b = Table::new();
+        b.set_reverse(true);
+        b.add_edge("y", "z");
+
+        a.merge(&b).unwrap();
+        assert_eq!(named_edges(&a), pairs(&[("y", "x"), ("z", "y")]));
+    }
+
+    #[test]
+    fn reverse_promotes_the_other_end_of_a_dag() {
+        let mut forward = Table::new();
+        let mut reverse = Table::new();
+        reverse.set_reverse(true);
+        for t in [&mut forward, &mut reverse] {
+            t.add_edge("a", "b");
+            t.add_edge("b", "c");
+            t.add_edge("a", "c");
+            t.pagerank().unwrap();
+        }
+
+        assert_eq!(forward.top_k(1)[0].0, "c");
+        assert_eq!(reverse.top_k(1)[0].0, "a");
+    }
+
+    #[test]
+    fn gauss_seidel_agrees_with_jacobi() {
+        // A cycle with a dangling node, whose mass Gauss-Seidel must not lose
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3)];
+        let mut jacobi = numeric_graph(&edges);
+        let mut gauss_seidel = numeric_graph(&edges);
+        gauss_seidel.set_iteration_method(IterationMethod::GaussSeidel);
+
+        assert!(jacobi.pagerank().unwrap().converged);
+        assert!(gauss_seidel.pagerank().unwrap().converged);
+        assert!(gauss_seidel.check_pagerank().is_ok());
+        let sum: PrFloat = gauss_seidel.pagerank_scores().iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "sum is {}", sum);
+        assert_close(jacobi.pagerank_scores(), gauss_seidel.pagerank_scores(), 1e-4);
+    }
+
+    #[test]
+    fn whitespace_mode_splits_fields() {
+        let mut t = Table::new();
+        t.set_whitespace(true);
+        assert_eq!(t.split_line("a\tb  1"), Some(("a", "b", None)));
+        t.set_weighted(true);
+        assert_eq!(t.split_line("  a b\t2.5 extra"), Some(("a", "b", Some("2.5"))));
+
+        let mut t = Table::new();
+        t.set_whitespace(true);
+        t.set_numeric(true);
+        t.read_from_reader("0 1 5\n1 2\n".as_bytes()).unwrap();
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn summary_counts_dangling_nodes_among_used_indices() {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.add_edge_idx(0, 5);
+        t.add_edge_idx(1, 0);
+
+        let stats = t.summary();
+        assert_eq!(stats.nodes, 3);
+        assert_eq!(stats.dangling, 1);
+        assert_eq!(t.dangling_nodes(), vec![5]);
+    }
+
+    #[test]
+    fn dangling_fraction_ignores_unused_indices() {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.add_edge_idx(0, 5);
+        t.add_edge_idx(1, 0);
+
+        assert!((t.dangling_fraction() - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn doubled_edge_counts_only_with_multi_edges() {
+        let single = doubled_edge(false);
+        assert_eq!(single.num_edges(), 4);
+        assert_eq!(single.get_rank_by_name("b"), single.get_rank_by_name("c"));
+
+        let multi = doubled_edge(true);
+        assert_eq!(multi.num_edges(), 5);
+        assert!(multi.get_rank_by_name("b").unwrap() > multi.get_rank_by_name("c").unwrap());
+    }
+
+    #[test]
+    fn self_loop_policies_change_the_rank() {
+        let keep = self_loop_rank(SelfLoopPolicy::Keep);
+        let drop = self_loop_rank(SelfLoopPolicy::Drop);
+        let redistribute = self_loop_rank(SelfLoopPolicy::Redistribute);
+
+        // Without the self-loop the two nodes are symmetric
+        assert!((drop - 0.5).abs() < 1e-4);
+        assert!(keep > redistribute);
+        assert!(redistribute > drop);
+    }
+
+    #[test]
+    fn all_dangling_graph_is_uniform() {
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_num_rows(4);
+        t.pagerank().unwrap();
+
+        assert_close(t.pagerank_scores(), &[0.25; 4], 1e-6);
+        assert!(t.check_pagerank().is_ok());
+    }
+
+    #[test]
+    fn ties_break_by_name() {
+        let mut t = Table::new();
+        for (from, to) in [("d", "b"), ("b", "c"), ("c", "a"), ("a", "d")] {
+            t.add_edge(from, to);
+        }
+        t.pagerank().unwrap();
+
+        let names: Vec<String> = t.top_k(4).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["a", "b", "c", "d"]);
+
+        let mut out = Vec::new();
+        t.write_pagerank_sorted(&mut out, SortOrder::Ascending).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let names: Vec<&str> = out.lines().filter_map(|line| line.split(" = ").next()).collect();
+        assert_eq!(names, ["a", "b", "c", "d", "s"]);
+    }
+
+    #[test]
+    fn undirected_load_equals_reversed_edges() {
+        let mut undirected = Table::new();
+        undirected.set_undirected(true);
+        undirected.set_delim(" ");
+        undirected.read_from_reader("a b\nb c\nc c\n".as_bytes()).unwrap();
+        undirected.pagerank().unwrap();
+
+        let mut manual = Table::new();
+        manual.set_delim(" ");
+        manual.read_from_reader("a b\nb a\nb c\nc b\nc c\n".as_bytes()).unwrap();
+        manual.pagerank().unwrap();
+
+        assert_eq!(undirected.num_edges(), manual.num_edges());
+        assert_eq!(named_edges(&undirected), named_edges(&manual));
+        assert_close(undirected.pagerank_scores(), manual.pagerank_scores(), 1e-12);
+    }
+
+    #[test]
+    fn comment_and_header_lines_are_not_skipped_lines() {
+        let mut t = Table::new();
+        t.set_delim(" ");
+        t.read_from_reader("# source target\na b\nb c\n".as_bytes()).unwrap();
+        assert_eq!(t.get_skipped_lines(), 0);
+        assert_eq!(t.num_nodes(), 3);
+
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.set_skip_header(1);
+        t.read_from_reader("source target\n0 1\n1 2\n".as_bytes()).unwrap();
+        assert_eq!(t.get_skipped_lines(), 0);
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn large_in_degree_row_stays_sorted() {
+        const N: usize = 100_000;
+        let mut t = Table::new();
+        t.set_numeric(true);
+        // Out of order, with every source repeated
+        for k in (1..=N).rev().chain((1..=N).step_by(7)) {
+            t.add_edge_idx(k, 0);
+        }
+
+        let row = &t.rows[0];
+        assert_eq!(row.len(), N);
+        assert!(row.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn quoted_names_contain_the_delimiter() {
+        let mut t = Table::new();
+        t.set_delim(",");
+        t.set_trim_quotes(true);
+        t.read_from_reader("\"a,b\",c\n".as_bytes()).unwrap();
+
+        assert_eq!(named_edges(&t), pairs(&[("a,b", "c")]));
+    }
+
+    #[test]
+    fn mirrored_edges_are_deduplicated() {
+        let mut t = Table::new();
+        t.set_quiet(true);
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.set_dedup_symmetric(true);
+        t.read_file(&data_file("bull-mirrored.txt")).unwrap();
+
+        let mut bull = Table::new();
+        bull.set_quiet(true);
+        bull.set_numeric(true);
+        bull.set_delim(" ");
+        bull.read_file(&data_file("bull.txt")).unwrap();
+
+        assert_eq!(t.get_symmetric_duplicates(), 5);
+        assert_eq!(named_edges(&t), named_edges(&bull));
+    }
+
+    #[test]
+    fn nodes_without_names_print_their_index() {
+        let mut t = Table::new();
+        t.add_edge("a", "b");
+        t.add_edge_idx(1, 5);
+        t.pagerank().unwrap();
+
+        let mut out = Vec::new();
+        t.write_pagerank_v(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let names: Vec<&str> = out.lines().filter_map(|line| line.split(" = ").next()).collect();
+        assert_eq!(names, ["a", "b", "2", "3", "4", "5", "s"]);
+    }
+
+    #[test]
+    fn resumed_checkpoint_continues_the_calculation() {
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 1)];
+        let mut full = numeric_graph(&edges);
+        let full_result = full.pagerank().unwrap();
+
+        let mut first = numeric_graph(&edges);
+        first.set_max_iterations(5).unwrap();
+        assert!(!first.pagerank().unwrap().converged);
+        let mut saved = Vec::new();
+        first.save_checkpoint(&mut saved).unwrap();
+
+        let mut resumed = numeric_graph(&edges);
+        resumed.resume_from(&mut saved.as_slice()).unwrap();
+        let resumed_result = resumed.pagerank().unwrap();
+        assert_eq!(resumed_result.iterations, full_result.iterations);
+        assert_eq!(resumed.pagerank_scores(), full.pagerank_scores());
+
+        let mut larger = numeric_graph(&edges);
+        larger.add_edge_idx(3, 4);
+        assert!(larger.resume_from(&mut saved.as_slice()).is_err());
+    }
+
+    #[test]
+    fn retain_nodes_renumbers_the_kept_nodes() {
+        let mut t = Table::new();
+        t.set_self_loops(SelfLoopPolicy::Redistribute);
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "d")] {
+            t.add_edge(from, to);
+        }
+        t.retain_nodes(|_, name| name != "b");
+
+        assert_eq!(t.get_num_rows(), 3);
+        let names: Vec<String> = (0..3).map(|k| t.get_node_name(k)).collect();
+        assert_eq!(names, ["a", "c", "d"]);
+        // The redistributed self-loop of d is kept, at its new index
+        assert_eq!(named_edges(&t), pairs(&[("c", "a"), ("c", "d"), ("d", "d")]));
+        assert_eq!(t.num_outgoing, [0, 2, 1]);
+        assert_eq!(t.num_edges(), 3);
+    }
+
+    #[test]
+    fn retain_edges_keeps_the_weights_of_kept_links() {
+        let mut t = Table::new();
+        t.set_weighted(true);
+        t.add_weighted_edge("a", "b", 2.0).unwrap();
+        t.add_weighted_edge("a", "c", 1.0).unwrap();
+        t.add_weighted_edge("b", "c", 3.0).unwrap();
+        t.retain_edges(|from, to| (from, to) != (0, 2));
+
+        assert_eq!(named_edges(&t), pairs(&[("a", "b"), ("b", "c")]));
+        assert_eq!(t.out_weights, [2.0, 3.0, 0.0]);
+        assert_eq!(t.weights[2], [3.0]);
+    }
+
+    #[test]
+    fn retain_edges_drops_self_loops_and_their_repeats() {
+        let mut t = Table::new();
+        t.set_self_loops(SelfLoopPolicy::Redistribute);
+        t.set_allow_multi_edges(true);
+        for (from, to) in [("a", "a"), ("a", "a"), ("a", "b"), ("b", "a"), ("b", "a")] {
+            t.add_edge(from, to);
+        }
+        assert_eq!(t.num_edges(), 5);
+
+        t.retain_edges(|from, to| from != to);
+        assert_eq!(t.num_outgoing, [1, 1]);
+        assert_eq!(t.num_edges(), 3);
+    }
+
+    #[test]
+    fn retain_edges_after_leaving_weighted_multi_edge_mode() {
+        let mut t = Table::new();
+        t.set_weighted(true);
+        t.set_allow_multi_edges(true);
+        t.add_weighted_edge("a", "b", 0.5).unwrap();
+        t.set_weighted(false);
+        t.retain_edges(|_, _| true);
+
+        assert_eq!(t.num_edges(), 1);
+    }
+
+    #[test]
+    fn node_filter_skips_edges_of_edge_lists() {
+        let whitelist = temp_file("whitelist.txt", "a\nb\nc\n");
+        let mut t = Table::new();
+        t.set_delim(" ");
+        t.load_node_whitelist(&whitelist).unwrap();
+        t.read_from_reader("a b\nb c\nc d\nd a\n".as_bytes()).unwrap();
+
+        assert_eq!(named_edges(&t), pairs(&[("a", "b"), ("b", "c")]));
+        assert_eq!(t.get_filtered_edges(), 2);
+        assert_eq!(t.num_nodes(), 3);
+
+        let blacklist = temp_file("blacklist.txt", "3\n");
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.load_node_blacklist(&blacklist).unwrap();
+        t.read_from_reader("0 1\n1 3\n3 2\n2 0\n".as_bytes()).unwrap();
+
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(2, 0), (0, 1)]);
+        assert_eq!(t.get_filtered_edges(), 2);
+    }
+
+    #[test]
+    fn node_filter_skips_matrix_market_entries() {
+        let mtx = "%%MatrixMarket matrix coordinate pattern symmetric\n3 3 3\n2 1\n3 1\n3 2\n";
+
+        let blacklist = temp_file("mtx-blacklist.txt", "3\n");
+        let mut t = Table::new();
+        t.load_node_blacklist(&blacklist).unwrap();
+        t.read_mtx_lines(mtx.as_bytes()).unwrap();
+        assert_eq!(named_edges(&t), pairs(&[("1", "2"), ("2", "1")]));
+        assert_eq!(t.get_filtered_edges(), 2);
+
+        // Zero-based indices for numeric input
+        let whitelist = temp_file("mtx-whitelist.txt", "1\n2\n");
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.load_node_whitelist(&whitelist).unwrap();
+        t.read_mtx_lines(mtx.as_bytes()).unwrap();
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(2, 1), (1, 2)]);
+        assert_eq!(t.get_filtered_edges(), 2);
+    }
+
+    #[test]
+    fn labelled_numeric_edges_read_back() {
+        let labels = temp_file("labels.txt", "0 zero\n1 one\n2 two\n");
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.read_from_reader("0 1\n1 2\n2 0\n".as_bytes()).unwrap();
+        t.load_labels(&labels).unwrap();
+        assert_eq!(t.get_node_name(1), "one");
+
+        let mut out = Vec::new();
+        t.write_edges(&mut out).unwrap();
+        let mut read_back = Table::new();
+        read_back.set_numeric(true);
+        read_back.set_delim(" ");
+        read_back.read_from_reader(out.as_slice()).unwrap();
+
+        assert_eq!(read_back.get_skipped_lines(), 0);
+        assert_eq!(read_back.edges().collect::<Vec<_>>(), t.edges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn estimated_iterations_extrapolates_the_history() {
+        // An undirected path, whose differences shrink by a steady ratio
+        let edges = [(0, 1), (1, 0), (1, 2), (2, 1), (2, 3), (3, 2)];
+        let mut full = numeric_graph(&edges);
+        full.set_record_history(true);
+        let iterations = full.pagerank().unwrap().iterations;
+        assert_eq!(full.get_convergence_history().len(), iterations);
+        // A converged calculation needs no more iterations
+        assert_eq!(full.estimated_iterations(), iterations);
+
+        let mut probe = numeric_graph(&edges);
+        // Without a history the differences shrink by alpha from one
+        let alpha = probe.get_alpha();
+        let from_alpha = (probe.get_convergence().ln() / alpha.ln()).ceil() as usize;
+        assert_eq!(probe.estimated_iterations(), from_alpha);
+
+        probe.set_fixed_iterations(Some(5));
+        probe.set_record_history(true);
+        probe.pagerank().unwrap();
+        let history = probe.get_convergence_history();
+        assert_eq!(history.len(), 5);
+        let ratio = history[4] / history[3];
+        let remaining = (probe.get_convergence() / history[4]).ln() / ratio.ln();
+        let estimate = probe.estimated_iterations();
+        assert_eq!(estimate, 5 + remaining.ceil() as usize);
+        assert!(estimate.abs_diff(iterations) <= 1, "{} for {} iterations", estimate, iterations);
+    }
+
+    #[test]
+    fn merge_matches_nodes_by_name() {
+        let mut a = Table::new();
+        a.add_edge("x", "y");
+        a.add_edge("y", "z");
+        let mut b = Table::new();
+        for (from, to) in [("z", "w"), ("w", "x"), ("x", "y")] {
+            b.add_edge(from, to);
+        }
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.num_nodes(), 4);
+        // The nodes of a keep their indices, and the repeated link is ignored
+        assert_eq!(a.get_node_name(0), "x");
+        assert_eq!(a.get_node_name(3), "w");
+        assert_eq!(a.num_edges(), 4);
+        assert_eq!(named_edges(&a), pairs(&[("w", "x"), ("x", "y"), ("y", "z"), ("z", "w")]));
+    }
+
+    #[test]
+    fn merge_adds_the_labels_of_numeric_tables() {
+        let labels = temp_file("merge-labels.txt", "0 zero\n2 two\n");
+        let mut a = numeric_graph(&[(0, 1)]);
+        let mut b = numeric_graph(&[(1, 2)]);
+        b.set_delim(" ");
+        b.load_labels(&labels).unwrap();
+
+        a.merge(&b).unwrap();
+        assert_eq!(a.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        let names: Vec<String> = (0..3).map(|k| a.get_node_name(k)).collect();
+        assert_eq!(names, ["zero", "1", "two"]);
+    }
+
+    #[test]
+    fn merge_mismatch_leaves_the_table_unchanged() {
+        let mut named = Table::new();
+        named.add_edge("x", "y");
+        named.pagerank().unwrap();
+        let pr = named.pagerank_scores().to_vec();
+        let mut numeric = numeric_graph(&[(0, 1), (1, 2)]);
+
+        assert!(matches!(named.merge(&numeric), Err(PagerankError::MergeMismatch)));
+        assert_eq!(named_edges(&named), pairs(&[("x", "y")]));
+        assert_eq!(named.get_num_rows(), 2);
+        assert_eq!(named.pagerank_scores(), pr);
+
+        assert!(matches!(numeric.merge(&named), Err(PagerankError::MergeMismatch)));
+        assert_eq!(numeric.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!(numeric.get_num_rows(), 3);
+    }
+
+    #[test]
+    fn remove_node_keeps_the_rest_of_the_graph() {
+        let mut t = Table::new();
+        t.set_warm_start(true);
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "b")] {
+            t.add_edge(from, to);
+        }
+        t.pagerank().unwrap();
+        let before: Vec<PrFloat> = ["a", "b", "d"]
+            .iter()
+            .map(|name| t.get_rank_by_name(name).unwrap())
+            .collect();
+
+        assert!(!t.remove_node("x"));
+        assert!(t.remove_node("c"));
+        assert_eq!(t.get_num_rows(), 3);
+        assert_eq!(t.get_node_name(2), "d");
+        assert_eq!(named_edges(&t), pairs(&[("a", "b"), ("d", "b")]));
+        assert_eq!(t.rows, [vec![], vec![0, 2], vec![]]);
+        assert_eq!(t.num_outgoing, [1, 0, 1]);
+
+        // The kept pagerank is renormalized, for the next calculation to start from
+        let sum: PrFloat = before.iter().sum();
+        let expected: Vec<PrFloat> = before.iter().map(|pr| pr / sum).collect();
+        assert_close(t.pagerank_scores(), &expected, 1e-6);
+        assert!((t.pagerank_scores().iter().sum::<PrFloat>() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pagerank_local_ranks_the_neighborhood() {
+        let path = [("a", "b"), ("b", "c"), ("c", "d"), ("d", "e")];
+        let mut t = Table::new();
+        for (from, to) in path {
+            t.add_edge(from, to);
+        }
+
+        assert!(t.pagerank_local("x", 2).is_none());
+        let seed_only = t.pagerank_local("b", 0).unwrap();
+        assert_eq!(seed_only.len(), 1);
+        assert!((seed_only["b"] - 1.0).abs() < 1e-6);
+        // Hops beyond the end of the path reach every node after the seed
+        assert_eq!(t.pagerank_local("c", 10).unwrap().len(), 3);
+
+        // Two hops from a reach c, which is dangling in the neighborhood
+        let mut sub = Table::new();
+        for (from, to) in &path[..2] {
+            sub.add_edge(from, to);
+        }
+        sub.pagerank().unwrap();
+        let local = t.pagerank_local("a", 2).unwrap();
+        assert_eq!(local.len(), 3);
+        for name in ["a", "b", "c"] {
+            assert!((local[name] - sub.get_rank_by_name(name).unwrap()).abs() < 1e-6, "{}", name);
+        }
+
+        // The personalization is restricted to the neighborhood
+        let c = t.get_node_index("c").unwrap();
+        let e = t.get_node_index("e").unwrap();
+        t.set_personalization(HashMap::from([(c, 1.0), (e, 3.0)])).unwrap();
+        sub.set_personalization(HashMap::from([(2, 1.0)])).unwrap();
+        sub.reset_results();
+        sub.pagerank().unwrap();
+        let local = t.pagerank_local("a", 2).unwrap();
+        for name in ["a", "b", "c"] {
+            assert!((local[name] - sub.get_rank_by_name(name).unwrap()).abs() < 1e-6, "{}", name);
+        }
+        assert!(local["c"] > local["a"]);
+    }
+
+    #[test]
+    fn hits_scores_follow_the_golden_ratio() {
+        // a links to b and c, d links to b
+        let mut t = Table::new();
+        for (from, to) in [("a", "b"), ("a", "c"), ("d", "b")] {
+            t.add_edge(from, to);
+        }
+        let (authority, hub) = t.hits(100);
+
+        // The principal eigenvectors of [[2, 1], [1, 1]], normalized
+        let phi = (1.0 + (5.0 as PrFloat).sqrt()) / 2.0;
+        assert_close(&authority, &[0.0, 1.0 / phi, 1.0 - 1.0 / phi, 0.0], 1e-6);
+        assert_close(&hub, &[1.0 / phi, 0.0, 0.0, 1.0 - 1.0 / phi], 1e-6);
+        assert!(t.pagerank_scores().is_empty());
+    }
+
+    #[test]
+    fn eigenvector_centrality_of_a_triangle_with_a_tail() {
+        // A triangle in both directions, and a node linking into it
+        let t = numeric_graph(&[(0, 1), (1, 0), (1, 2), (2, 1), (0, 2), (2, 0), (3, 0)]);
+        let centrality = t.eigenvector_centrality(1000, 1e-12);
+
+        // Nothing links to 3, so its centrality goes to zero
+        assert_close(&centrality, &[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 0.0], 1e-6);
+        assert_eq!(t.eigenvector_centrality(0, 1e-12), [0.25; 4]);
+    }
+
+    #[test]
+    fn teleport_rescaling_equals_a_smaller_damping_factor() {
+        // Without dangling nodes the links keep alpha of the mass, so the
+        // rescaled vector is the pagerank with alpha / (alpha + beta)
+        let edges = [(0, 1), (1, 2), (2, 0), (0, 2), (3, 0)];
+        let mut t = numeric_graph(&edges);
+        t.set_alpha(0.5).unwrap();
+        t.set_teleport(0.25).unwrap();
+        assert!(t.pagerank().unwrap().converged);
+        assert!(t.check_pagerank().is_ok());
+
+        let mut standard = numeric_graph(&edges);
+        standard.set_alpha(2.0 / 3.0).unwrap();
+        standard.pagerank().unwrap();
+        assert_close(t.pagerank_scores(), standard.pagerank_scores(), 1e-5);
+
+        // A teleport probability of 1 - alpha is the standard one
+        t.set_teleport(0.5).unwrap();
+        t.reset_results();
+        t.pagerank().unwrap();
+        let mut default = numeric_graph(&edges);
+        default.set_alpha(0.5).unwrap();
+        default.pagerank().unwrap();
+        assert_close(t.pagerank_scores(), default.pagerank_scores(), 1e-9);
+    }
+
+    #[test]
+    fn personalized_pagerank_of_two_nodes() {
+        let mut t = Table::new();
+        t.add_edge("a", "b");
+        t.add_edge("b", "a");
+        t.set_alpha(0.5).unwrap();
+        let a = t.get_node_index("a").unwrap();
+        t.set_personalization(HashMap::from([(a, 2.0)])).unwrap();
+        assert_eq!(t.get_personalization().unwrap()[&a], 1.0);
+        t.pagerank().unwrap();
+
+        // pr(a) = alpha pr(b) + 1 - alpha and pr(b) = alpha pr(a)
+        assert!((t.get_rank_by_name("a").unwrap() - 2.0 / 3.0).abs() < 1e-5);
+        assert!((t.get_rank_by_name("b").unwrap() - 1.0 / 3.0).abs() < 1e-5);
+
+        for weights in [HashMap::from([(a, -1.0)]), HashMap::from([(a, 0.0)]), HashMap::new()] {
+            assert!(matches!(
+                t.set_personalization(weights),
+                Err(PagerankError::InvalidPersonalization)
+            ));
+        }
+    }
+
+    #[test]
+    fn weighted_links_split_the_rank_by_weight() {
+        let mut t = Table::new();
+        t.set_weighted(true);
+        t.set_delim(" ");
+        t.set_alpha(0.5).unwrap();
+        // Without a weight a link weighs one
+        t.read_from_reader("a b 3\na c 1\nb a\nc a 2.5\n".as_bytes()).unwrap();
+        t.pagerank().unwrap();
+
+        // pr(a) = (1 - pr(a)) / 2 + 1 / 6, and a gives 3/4 of its share to b
+        let expected = [("a", 4.0 / 9.0), ("b", 1.0 / 3.0), ("c", 2.0 / 9.0)];
+        for (name, pr) in expected {
+            assert!((t.get_rank_by_name(name).unwrap() - pr).abs() < 1e-5, "{}", name);
+        }
+
+        let mut t = Table::new();
+        t.set_weighted(true);
+        t.set_delim(" ");
+        assert!(matches!(
+            t.read_from_reader("a b 1\nb a -1\n".as_bytes()),
+            Err(PagerankError::ParseWeight { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn personalization_file_adds_the_weights_of_each_node() {
+        let seeds = temp_file("seeds.txt", "# node weight\na 3\nb 1\n\na 2\n");
+        let mut t = Table::new();
+        t.set_delim(" ");
+        t.read_from_reader("a b\nb c\nc a\n".as_bytes()).unwrap();
+        t.load_personalization(&seeds).unwrap();
+
+        let personalization = t.get_personalization().unwrap();
+        assert_eq!(personalization.len(), 2);
+        assert!((personalization[&0] - 5.0 / 6.0).abs() < 1e-12);
+        assert!((personalization[&1] - 1.0 / 6.0).abs() < 1e-12);
+
+        let unknown = temp_file("unknown-seeds.txt", "a 1\nd 1\n");
+        match t.load_personalization(&unknown) {
+            Err(PagerankError::UnknownNode { line, name }) => {
+                assert_eq!((line, name.as_str()), (2, "d"));
+            }
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn indices_above_the_maximum_are_rejected() {
+        let input = "0 1\n1 7\n2 3\n";
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.set_delim(" ");
+        t.set_max_index(Some(3));
+        assert!(matches!(
+            t.read_from_reader(input.as_bytes()),
+            Err(PagerankError::IndexOutOfRange { line: 2, index: 7, max: 3 })
+        ));
+        assert!(t.get_num_rows() <= 4);
+
+        t.set_lenient(true);
+        t.read_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(t.get_invalid_lines(), 1);
+        assert_eq!(t.get_num_rows(), 4);
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(0, 1), (2, 3)]);
+
+        let mtx = "%%MatrixMarket matrix coordinate pattern general\n5 5 1\n1 2\n";
+        assert!(matches!(
+            t.read_mtx_lines(mtx.as_bytes()),
+            Err(PagerankError::IndexOutOfRange { line: 2, index: 4, max: 3 })
+        ));
+    }
+
+    #[test]
+    fn exhausted_time_budget_keeps_the_start_vector() {
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3)];
+        let mut t = numeric_graph(&edges);
+        t.set_time_budget(Some(Duration::ZERO));
+        let result = t.pagerank().unwrap();
+
+        assert!(result.timed_out);
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 0);
+        assert_eq!(t.pagerank_scores(), [0.25; 4]);
+
+        // A budget that is not exhausted changes nothing
+        t.set_time_budget(Some(Duration::from_secs(3600)));
+        t.reset_results();
+        let result = t.pagerank().unwrap();
+        let mut unlimited = numeric_graph(&edges);
+        let unlimited_result = unlimited.pagerank().unwrap();
+        assert!(!result.timed_out);
+        assert_eq!(result.iterations, unlimited_result.iterations);
+        assert_eq!(t.pagerank_scores(), unlimited.pagerank_scores());
+    }
+
+    #[test]
+    fn matrix_market_bull_matches_the_reference_pagerank() {
+        // The edges of bull.txt, with one-based indices
+        let mtx = "%%MatrixMarket matrix coordinate pattern general\n\
+                   % the bull graph\n\
+                   5 5 5\n1 2\n1 3\n2 3\n2 4\n3 5\n";
+        let mut t = Table::new();
+        t.set_numeric(true);
+        t.read_mtx_lines(mtx.as_bytes()).unwrap();
+        assert_eq!(t.edges().collect::<Vec<_>>(), vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 4)]);
+        t.pagerank().unwrap();
+        assert_close(t.pagerank_scores(), &reference_pagerank("bull-pr-p.txt"), 1e-4);
+
+        // Each entry of a symmetric matrix is a link in both directions
+        let mtx = "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n2 1 3.5\n2 2 1.0\n";
+        let mut t = Table::new();
+        t.set_weighted(true);
+        t.read_mtx_lines(mtx.as_bytes()).unwrap();
+        assert_eq!(named_edges(&t), pairs(&[("1", "2"), ("2", "1"), ("2", "2")]));
+        assert_eq!(t.out_weights[0], 3.5);
+
+        let mtx = "%%MatrixMarket matrix array real general\n2 2\n1.0\n";
+        assert!(matches!(
+            Table::new().read_mtx_lines(mtx.as_bytes()),
+            Err(PagerankError::InvalidMatrixMarket { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn json_output_escapes_names_and_sums_the_ranks() {
+        let mut t = Table::new();
+        t.add_edge("a\"b", "c\td");
+        t.pr = vec![0.25, 0.75];
+
+        let mut out = Vec::new();
+        t.write_pagerank_json(&mut out, None).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"ranks\": [{\"node\": \"a\\\"b\", \"rank\": 0.25}, \
+             {\"node\": \"c\\td\", \"rank\": 0.75}], \"sum\": 1.0}\n"
+        );
+
+        let mut out = Vec::new();
+        t.write_pagerank_json(&mut out, Some(1)).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"ranks\": [{\"node\": \"c\\td\", \"rank\": 0.75}], \"sum\": 0.75}\n"
+        );
+    }
+
+    #[test]
+    fn dot_output_labels_and_sizes_the_nodes() {
+        let mut t = Table::new();
+        t.add_edge("a", "b\"c");
+        t.pr = vec![0.2, 0.8];
+
+        let mut out = Vec::new();
+        t.write_dot(&mut out).unwrap();
+        // The widths go from 0.25 to 2 with the pagerank relative to the highest
+        assert_eq!(String::from_utf8(out).unwrap(), "digraph pagerank {\n    \
+            \"a\" [label=\"a\\n0.2000\", width=0.688];\n    \
+            \"b\\\"c\" [label=\"b\\\"c\\n0.8000\", width=2.000];\n    \
+            \"a\" -> \"b\\\"c\";\n\
+            }\n");
+    }
+
+    #[test]
+    fn read_files_reads_the_union_of_the_graphs() {
+        let first = temp_file("union-1.txt", "a b\nb c\n");
+        let second = temp_file("union-2.txt", "c a\nb c\n");
+        let mut t = Table::new();
+        t.set_quiet(true);
+        t.set_delim(" ");
+        t.read_files(&[first.clone(), second]).unwrap();
+
+        // The nodes are shared by name, and the repeated link is ignored
+        assert_eq!(t.num_nodes(), 3);
+        assert_eq!(named_edges(&t), pairs(&[("a", "b"), ("b", "c"), ("c", "a")]));
+        t.pagerank().unwrap();
+        assert_close(t.pagerank_scores(), &[1.0 / 3.0; 3], 1e-6);
+
+        let missing = std::env::temp_dir().join("pagerank-missing-file.txt");
+        assert!(matches!(t.read_files(&[first, missing]), Err(PagerankError::Io(_))));
+    }
+
+    #[test]
+    fn count_first_reserves_the_counted_rows() {
+        let mut names = HashSet::new();
+        let mut max_dim = 0;
+        let mut t = Table::new();
+        t.set_delim(" ");
+        t.count_rows("a b\nb c\n# a comment\nc a\n".as_bytes(), &mut names, &mut max_dim).unwrap();
+        assert_eq!(names.len(), 3);
+
+        t.set_numeric(true);
+        t.count_rows("0 1\n7 2\n".as_bytes(), &mut names, &mut max_dim).unwrap();
+        assert_eq!(max_dim, 8);
+
+        // Counting first builds the same graph
+        let mut counted = Table::new();
+        counted.set_quiet(true);
+        counted.set_numeric(true);
+        counted.set_delim(" ");
+        counted.set_count_first(true);
+        counted.read_file(&data_file("bull.txt")).unwrap();
+        assert_eq!(counted.get_num_rows(), 5);
+        counted.pagerank().unwrap();
+        assert_close(counted.pagerank_scores(), &reference_pagerank("bull-pr-p.txt"), 1e-4);
+    }
+}